@@ -48,8 +48,7 @@ export const useCommentStore = defineStore('comment', {
       try {
         this.isLoading = true
         const result = await invoke<CommentResult>('send_comment', {
-          bvid: video.bvid,
-          aid: video.aid,
+          video,
           content,
         })
         return result
@@ -62,7 +61,7 @@ export const useCommentStore = defineStore('comment', {
     },
 
     // 批量发送评论
-    async batchSendComments(videos: VideoItem[], content: string) {
+    async batchSendComments(videos: VideoItem[], content: string, dryRun = false) {
       // 先清理之前的任务
       this.stopPolling()
       this.currentBatchId = null
@@ -74,6 +73,7 @@ export const useCommentStore = defineStore('comment', {
         const batchId = await invoke<string>('batch_send_comments', {
           videos,
           content,
+          dryRun,
         })
         this.currentBatchId = batchId
         // 开始轮询状态
@@ -1,6 +1,6 @@
 import { defineStore } from 'pinia'
 import { invoke } from '@tauri-apps/api/core'
-import type { VideoItem, SearchResult, SearchOrder } from '~/types/bilibili'
+import type { VideoItem, SearchResult, SearchOrder, SearchFilters } from '~/types/bilibili'
 
 // 防抖定时器
 let searchDebounceTimer: ReturnType<typeof setTimeout> | null = null
@@ -13,6 +13,7 @@ export const useSearchStore = defineStore('search', {
     pageSize: 20,
     total: 0,
     order: 'totalrank' as SearchOrder,
+    filters: {} as SearchFilters,
     isLoading: false,
     selectedVideos: new Set<string>(), // 使用 bvid 作为 key
   }),
@@ -53,6 +54,7 @@ export const useSearchStore = defineStore('search', {
           page: this.page,
           pageSize: this.pageSize,
           order: this.order,
+          filters: this.filters,
         })
 
         this.results = result.items
@@ -89,6 +91,13 @@ export const useSearchStore = defineStore('search', {
       await this.search()
     },
 
+    // 设置筛选条件
+    async setFilters(filters: SearchFilters) {
+      this.filters = filters
+      this.page = 1
+      await this.search()
+    },
+
     // 切换视频选中状态
     toggleSelect(bvid: string) {
       if (this.selectedVideos.has(bvid)) {
@@ -19,6 +19,20 @@ export interface VideoItem {
   pubdate: number
   duration: string
   description: string
+  // 标题中搜索关键词的高亮位置 (字符串下标，左闭右开)
+  title_highlights?: HighlightRange[]
+}
+
+// 由链接或编号解析得到的视频
+export interface ResolvedVideo {
+  video: VideoItem
+  // 链接中 ?p= 指定的分 P
+  page: number | null
+}
+
+export interface HighlightRange {
+  start: number
+  end: number
 }
 
 export interface SearchResult {
@@ -28,15 +42,116 @@ export interface SearchResult {
   items: VideoItem[]
 }
 
+// 搜索筛选
+export type DurationFilter = 'all' | 'under_10' | '10_to_30' | '30_to_60' | 'over_60'
+
+export interface SearchFilters {
+  duration?: DurationFilter
+  tid?: number
+  pubtime_begin?: number
+  pubtime_end?: number
+}
+
+// 多页搜索进度事件 (事件名 search-collect)
+export interface SearchCollectEvent {
+  collect_id: string
+  page: number
+  items: VideoItem[]
+  collected: number
+  finished: boolean
+  cancelled: boolean
+  error?: string
+}
+
+// 候选视频筛选规则
+export interface VideoFilterRules {
+  min_play?: number
+  max_play?: number
+  min_danmaku?: number
+  max_danmaku?: number
+  min_duration_secs?: number
+  max_duration_secs?: number
+  max_age_days?: number
+  title_include: string[]
+  title_exclude: string[]
+  title_regex?: string
+  author_blocklist: string[]
+  author_allowlist: string[]
+}
+
+export interface FilterPreset {
+  id: string
+  name: string
+  rules: VideoFilterRules
+  created_at: number
+  updated_at: number
+}
+
+export interface RejectedVideo {
+  video: VideoItem
+  reason: string
+}
+
+export interface FilterOutcome {
+  kept: VideoItem[]
+  rejected: RejectedVideo[]
+}
+
 export interface CommentTemplate {
   id: string
   name: string
   content: string
+  category?: string
+  tags: string[]
   created_at: number
   updated_at: number
+  last_used_at?: number
+  revision: number
 }
 
-export type TaskStatus = 'pending' | 'running' | 'success' | 'failed' | 'cancelled'
+export type TemplateFormat = 'json' | 'csv'
+
+export type ImportConflict = 'skip' | 'overwrite' | 'duplicate'
+
+export interface ImportSummary {
+  created: number
+  overwritten: number
+  duplicated: number
+  skipped: number
+  errors: string[]
+}
+
+export interface TemplateRevision {
+  template_id: string
+  revision: number
+  name: string
+  content: string
+  created_at: number
+}
+
+export type TemplateSort = 'last_used' | 'updated_at' | 'created_at' | 'name'
+
+export interface TemplateFilter {
+  query?: string
+  category?: string
+  tags?: string[]
+  sort?: TemplateSort
+}
+
+export interface TemplateCategories {
+  categories: string[]
+  tags: string[]
+}
+
+export type TaskStatus =
+  | 'pending'
+  | 'running'
+  | 'success'
+  | 'failed'
+  | 'cancelled'
+  | 'skipped'
+  | 'would_send'
+  | 'struck'
 
 export interface CommentTask {
   id: string
@@ -44,6 +159,10 @@ export interface CommentTask {
   content: string
   status: TaskStatus
   error_msg?: string
+  error_code?: number
+  review_note?: string
+  rendered: boolean
+  rpid?: number
   created_at: number
   completed_at?: number
 }
@@ -52,6 +171,41 @@ export interface CommentResult {
   success: boolean
   rpid?: number
   error_msg?: string
+  error_code?: number
+}
+
+export type ErrorSeverity = 'warning' | 'error' | 'fatal'
+
+export interface ErrorCodeInfo {
+  code: number
+  message: string
+  message_en: string
+  retryable: boolean
+  severity: ErrorSeverity
+}
+
+export interface FailureCodeCount {
+  code?: number
+  message?: string
+  count: number
+}
+
+export interface TemplateEngagement {
+  sampled: number
+  avg_likes: number
+  avg_replies: number
+}
+
+export interface TemplateStats {
+  template_id: string
+  name?: string
+  batches: number
+  success: number
+  failed: number
+  success_rate: number
+  failure_codes: FailureCodeCount[]
+  last_used_at?: number
+  engagement?: TemplateEngagement
 }
 
 export interface BatchStatus {
@@ -60,9 +214,35 @@ export interface BatchStatus {
   completed: number
   success: number
   failed: number
+  skipped: number
+  dry_run: boolean
+  state: BatchState
+  created_by?: string
+  approved_by?: string
+  approved_at?: number
+  created_at: number
+  schedule?: BatchSchedule
+  next_run_at?: number
+  template: string
+  strict_template: boolean
+  template_id?: string
+  template_revision?: number
   tasks: CommentTask[]
 }
 
+export type BatchState = 'draft' | 'approved' | 'scheduled' | 'running' | 'finished' | 'cancelled'
+
+// 允许执行的时间段，格式 HH:MM[:SS]，结束早于开始表示跨越午夜
+export interface TimeWindow {
+  start: string
+  end: string
+}
+
+export interface BatchSchedule {
+  start_at?: string // RFC 3339，带时区偏移
+  windows: TimeWindow[]
+}
+
 export interface QrCodeData {
   url: string
   qrcode_key: string
@@ -77,7 +257,7 @@ export interface LoginPollResult {
 }
 
 // 搜索排序选项
-export type SearchOrder = 'totalrank' | 'click' | 'pubdate' | 'dm' | 'stow'
+export type SearchOrder = 'totalrank' | 'click' | 'pubdate' | 'dm' | 'stow' | 'scores'
 
 export const SEARCH_ORDER_OPTIONS = [
   { label: '综合排序', value: 'totalrank' },
@@ -85,4 +265,96 @@ export const SEARCH_ORDER_OPTIONS = [
   { label: '最新发布', value: 'pubdate' },
   { label: '最多弹幕', value: 'dm' },
   { label: '最多收藏', value: 'stow' },
+  { label: '最多评论', value: 'scores' },
 ] as const
+
+// 界面语言
+export type Locale = 'zh-CN' | 'en'
+
+// 应用设置
+export interface Settings {
+  locale: Locale
+  comment_interval_secs: number
+  task_max_retries: number
+  daily_comment_quota: number
+  allowlist_only: boolean
+  search_max_attempts: number
+  search_retry_delay_ms: number
+  collect_page_delay_ms: number
+  request_timeout_secs: number
+  user_agent: string
+}
+
+// 模板预览
+export type TemplateIssueKind =
+  | 'too_long'
+  | 'empty'
+  | 'unknown_placeholder'
+  | 'unclosed_brace'
+  | 'invalid_format'
+  | 'missing_user'
+  | 'banned_word'
+  | 'unsupported_char'
+
+export interface TemplateIssue {
+  kind: TemplateIssueKind
+  message: string
+}
+
+export interface TemplatePreview {
+  rendered?: string
+  char_count: number
+  issues: TemplateIssue[]
+}
+
+// 本地敏感词
+export type WordKind = 'plain' | 'regex'
+
+export interface SensitiveWord {
+  pattern: string
+  kind: WordKind
+}
+
+// 数据目录
+export type DataDirSource = 'command_line' | 'environment' | 'portable' | 'default'
+
+export interface DataLocation {
+  path: string
+  source: DataDirSource
+}
+
+// 存储文件问题
+// 数据备份
+export interface BackupSummary {
+  templates: number
+  template_revisions: number
+  batches: number
+  ledger_entries: number
+  filter_presets: number
+  access_list_entries: number
+  sensitive_words: number
+  credential: boolean
+  created_at: number
+  safety_backup?: string
+}
+
+// 屏蔽/允许名单
+export type AccessListKind = 'blocklist' | 'allowlist'
+export type AccessListTarget = 'author' | 'video'
+
+export interface AccessListEntry {
+  kind: AccessListKind
+  target: AccessListTarget
+  value: string
+  reason?: string
+  created_at: number
+  updated_at: number
+}
+
+export interface StorageIssue {
+  file: string
+  error: string
+  quarantined_to?: string
+  restored: boolean
+  occurred_at: number
+}
@@ -3,27 +3,35 @@ use parking_lot::RwLock;
 use reqwest::header::{HeaderMap, HeaderValue, COOKIE, REFERER, USER_AGENT};
 use reqwest::Client;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::models::LoginCredential;
+use crate::models::{LoginCredential, DEFAULT_USER_AGENT};
+use crate::storage::settings;
 
 /// B站 API 客户端
 pub struct BiliClient {
-    client: Client,
+    /// HTTP 客户端及其超时时间 (秒)，超时设置变更时重建
+    client: RwLock<(u64, Client)>,
     credential: Arc<RwLock<Option<LoginCredential>>>,
 }
 
 /// 全局客户端实例
-pub static BILI_CLIENT: Lazy<BiliClient> = Lazy::new(|| BiliClient::new());
+pub static BILI_CLIENT: Lazy<BiliClient> = Lazy::new(BiliClient::new);
+
+/// 创建指定超时时间的 HTTP 客户端
+fn build_client(timeout_secs: u64) -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .expect("Failed to create HTTP client")
+}
 
 impl BiliClient {
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
+        let timeout_secs = settings::current().request_timeout_secs;
 
         Self {
-            client,
+            client: RwLock::new((timeout_secs, build_client(timeout_secs))),
             credential: Arc::new(RwLock::new(None)),
         }
     }
@@ -54,13 +62,10 @@ impl BiliClient {
     pub fn build_headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
 
-        headers.insert(
-            USER_AGENT,
-            HeaderValue::from_static(
-                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
-                 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
-            ),
-        );
+        // User-Agent 从设置读取，无效时使用默认值
+        let user_agent = HeaderValue::from_str(settings::current().user_agent.trim())
+            .unwrap_or_else(|_| HeaderValue::from_static(DEFAULT_USER_AGENT));
+        headers.insert(USER_AGENT, user_agent);
 
         headers.insert(
             REFERER,
@@ -76,9 +81,20 @@ impl BiliClient {
         headers
     }
 
-    /// 获取底层 HTTP 客户端
-    pub fn client(&self) -> &Client {
-        &self.client
+    /// 获取底层 HTTP 客户端 (超时设置变更后自动重建)
+    pub fn client(&self) -> Client {
+        let timeout_secs = settings::current().request_timeout_secs;
+        {
+            let current = self.client.read();
+            if current.0 == timeout_secs {
+                return current.1.clone();
+            }
+        }
+
+        let client = build_client(timeout_secs);
+        *self.client.write() = (timeout_secs, client.clone());
+        log::info!("HTTP 请求超时已更新为 {} 秒", timeout_secs);
+        client
     }
 }
 
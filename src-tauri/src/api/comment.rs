@@ -4,12 +4,13 @@ use std::time::Duration;
 use tokio::time::sleep;
 
 use super::{BiliApiError, BILI_CLIENT};
-use crate::models::CommentResult;
+use crate::models::{CommentResult, ReplyEngagement};
+use crate::moderation;
+use crate::storage::settings;
 
 const COMMENT_ADD_URL: &str = "https://api.bilibili.com/x/v2/reply/add";
-
-/// 评论间隔时间 (秒)
-const COMMENT_INTERVAL_SECS: u64 = 5;
+const COMMENT_LIST_URL: &str = "https://api.bilibili.com/x/v2/reply";
+const COMMENT_DETAIL_URL: &str = "https://api.bilibili.com/x/v2/reply/detail";
 
 /// 安全截取字符串 (处理中文等多字节字符)
 fn truncate_str(s: &str, max_chars: usize) -> String {
@@ -36,6 +37,21 @@ struct CommentData {
     rpid: Option<u64>,
 }
 
+/// 评论详情响应数据
+#[derive(Debug, Deserialize)]
+struct ReplyDetailData {
+    root: Option<ReplyInfo>,
+}
+
+/// 评论信息
+#[derive(Debug, Deserialize)]
+struct ReplyInfo {
+    #[serde(default)]
+    like: u64,
+    #[serde(default)]
+    rcount: u64,
+}
+
 /// 发送评论
 pub async fn send_comment(aid: u64, content: &str) -> Result<CommentResult, BiliApiError> {
     let client = &BILI_CLIENT;
@@ -49,6 +65,13 @@ pub async fn send_comment(aid: u64, content: &str) -> Result<CommentResult, Bili
         .get_csrf()
         .ok_or_else(|| BiliApiError::NotLoggedIn)?;
 
+    // 本地敏感词预检，命中时不发送请求
+    let matched = moderation::check(content);
+    if !matched.is_empty() {
+        log::warn!("⚠️ 评论内容命中本地敏感词: aid={}, 词={:?}", aid, matched);
+        return Err(BiliApiError::SensitiveContent(matched.join(", ")));
+    }
+
     let headers = client.build_headers();
 
     let content_preview = truncate_str(content, 30);
@@ -78,16 +101,18 @@ pub async fn send_comment(aid: u64, content: &str) -> Result<CommentResult, Bili
             resp.code,
             resp.message
         );
+        let code = resp.code;
         return Ok(CommentResult {
             success: false,
             rpid: None,
             error_msg: Some(
                 BiliApiError::ApiError {
-                    code: resp.code,
+                    code,
                     message: resp.message,
                 }
                 .to_user_message(),
             ),
+            error_code: Some(code),
         });
     }
 
@@ -98,21 +123,95 @@ pub async fn send_comment(aid: u64, content: &str) -> Result<CommentResult, Bili
         success: true,
         rpid,
         error_msg: None,
+        error_code: None,
     })
 }
 
+/// 检查视频评论区是否可用 (评论区关闭、视频不存在时返回对应错误)
+pub async fn check_comment_area(aid: u64) -> Result<(), BiliApiError> {
+    let client = &BILI_CLIENT;
+    let headers = client.build_headers();
+
+    let response: Response = client
+        .client()
+        .get(COMMENT_LIST_URL)
+        .headers(headers)
+        .query(&[
+            ("oid", aid.to_string()),
+            ("type", "1".to_string()),
+            ("pn", "1".to_string()),
+            ("ps", "1".to_string()),
+        ])
+        .send()
+        .await?;
+
+    let resp: BiliResponse<serde_json::Value> = response.json().await?;
+
+    if resp.code != 0 {
+        log::warn!(
+            "⚠️ 评论区不可用: aid={}, code={}, message={}",
+            aid,
+            resp.code,
+            resp.message
+        );
+        return Err(BiliApiError::ApiError {
+            code: resp.code,
+            message: resp.message,
+        });
+    }
+
+    Ok(())
+}
+
+/// 获取评论的点赞数和回复数 (评论已被删除时返回 None)
+pub async fn get_reply_engagement(
+    aid: u64,
+    rpid: u64,
+) -> Result<Option<ReplyEngagement>, BiliApiError> {
+    let client = &BILI_CLIENT;
+    let headers = client.build_headers();
+
+    let response: Response = client
+        .client()
+        .get(COMMENT_DETAIL_URL)
+        .headers(headers)
+        .query(&[
+            ("oid", aid.to_string()),
+            ("type", "1".to_string()),
+            ("root", rpid.to_string()),
+        ])
+        .send()
+        .await?;
+
+    let resp: BiliResponse<ReplyDetailData> = response.json().await?;
+
+    match resp.code {
+        0 => Ok(resp.data.and_then(|d| d.root).map(|root| ReplyEngagement {
+            likes: root.like,
+            replies: root.rcount,
+        })),
+        // 评论不存在 (已被删除或折叠)
+        12022 | 12006 => Ok(None),
+        code => Err(BiliApiError::ApiError {
+            code,
+            message: resp.message,
+        }),
+    }
+}
+
 /// 发送评论 (带频率限制)
 pub async fn send_comment_with_rate_limit(
     aid: u64,
     content: &str,
 ) -> Result<CommentResult, BiliApiError> {
-    log::debug!("⏳ 等待 {} 秒后发送评论...", COMMENT_INTERVAL_SECS);
+    let interval = get_comment_interval();
+    log::debug!("⏳ 等待 {} 秒后发送评论...", interval);
     // 先等待间隔时间
-    sleep(Duration::from_secs(COMMENT_INTERVAL_SECS)).await;
+    sleep(Duration::from_secs(interval)).await;
     send_comment(aid, content).await
 }
 
-/// 获取评论间隔时间
+/// 获取评论间隔时间 (秒)，从设置读取
 pub fn get_comment_interval() -> u64 {
-    COMMENT_INTERVAL_SECS
+    settings::current().comment_interval_secs
 }
@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use super::error_code::{self, ErrorSeverity};
+use crate::i18n::{self, Msg};
+
 /// B站 API 错误类型
 #[derive(Error, Debug)]
 pub enum BiliApiError {
@@ -18,6 +21,9 @@ pub enum BiliApiError {
     #[error("评论失败: {0}")]
     CommentFailed(String),
 
+    #[error("评论内容包含本地敏感词: {0}")]
+    SensitiveContent(String),
+
     #[error("二维码已过期")]
     QrCodeExpired,
 
@@ -32,25 +38,51 @@ pub enum BiliApiError {
 }
 
 impl BiliApiError {
-    /// 转换为用户友好的错误信息
+    /// 转换为用户友好的错误信息 (按当前语言)
     pub fn to_user_message(&self) -> String {
         match self {
-            Self::NotLoggedIn => "请先登录".to_string(),
-            Self::RateLimited => "操作过于频繁，请稍等几秒后重试".to_string(),
-            Self::QrCodeExpired => "二维码已过期，请刷新".to_string(),
-            Self::ApiError { code, message } => match *code {
-                -101 => "账号未登录".to_string(),
-                -111 => "csrf校验失败".to_string(),
-                -400 => "请求错误".to_string(),
-                -404 => "视频不存在".to_string(),
-                12002 => "评论内容包含敏感词".to_string(),
-                12009 => "评论发送太频繁".to_string(),
-                12015 => "需要输入验证码".to_string(),
-                12016 => "您的账号存在异常，请完成验证后重试".to_string(),
-                12025 => "评论区已关闭".to_string(),
-                _ => format!("错误 {}: {}", code, message),
+            Self::Network(e) => i18n::tf(Msg::NetworkError, &[e]),
+            Self::NotLoggedIn => i18n::t(Msg::NotLoggedIn).to_string(),
+            Self::RateLimited => i18n::t(Msg::RateLimited).to_string(),
+            Self::QrCodeExpired => i18n::t(Msg::QrCodeExpired).to_string(),
+            Self::ApiError { code, message } => match error_code::lookup(*code) {
+                Some(info) => info.localized_message().to_string(),
+                None => i18n::tf(Msg::UnknownApiError, &[code, message]),
             },
-            _ => self.to_string(),
+            Self::CommentFailed(msg) => i18n::tf(Msg::CommentFailed, &[msg]),
+            Self::SensitiveContent(words) => i18n::tf(Msg::SensitiveContent, &[words]),
+            Self::ParseError(msg) => i18n::tf(Msg::ParseError, &[msg]),
+            Self::IoError(e) => i18n::tf(Msg::IoError, &[e]),
+            Self::Other(msg) => msg.clone(),
+        }
+    }
+
+    /// B站返回的错误码 (非 API 错误时为 None)
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Self::ApiError { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// 是否值得重试
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::RateLimited => true,
+            Self::ApiError { code, .. } => error_code::lookup(*code).is_some_and(|i| i.retryable),
+            _ => false,
+        }
+    }
+
+    /// 错误严重程度
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            Self::NotLoggedIn => ErrorSeverity::Fatal,
+            Self::Network(_) | Self::RateLimited => ErrorSeverity::Warning,
+            Self::ApiError { code, .. } => error_code::lookup(*code)
+                .map(|i| i.severity)
+                .unwrap_or(ErrorSeverity::Error),
+            _ => ErrorSeverity::Error,
         }
     }
 }
@@ -61,3 +93,35 @@ impl From<BiliApiError> for String {
         err.to_user_message()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(code: i32) -> BiliApiError {
+        BiliApiError::ApiError {
+            code,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn severity_follows_error_code_table() {
+        assert_eq!(api_error(-101).severity(), ErrorSeverity::Fatal);
+        assert!(!api_error(-101).is_retryable());
+        assert_eq!(api_error(-412).severity(), ErrorSeverity::Warning);
+        assert!(api_error(-412).is_retryable());
+    }
+
+    #[test]
+    fn unknown_code_is_non_retryable_error() {
+        assert_eq!(api_error(99999).severity(), ErrorSeverity::Error);
+        assert!(!api_error(99999).is_retryable());
+    }
+
+    #[test]
+    fn transient_errors_are_retryable() {
+        assert!(BiliApiError::RateLimited.is_retryable());
+        assert_eq!(BiliApiError::NotLoggedIn.severity(), ErrorSeverity::Fatal);
+    }
+}
@@ -0,0 +1,234 @@
+use serde::Serialize;
+
+use crate::i18n::{self, Locale};
+
+/// 错误严重程度
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    /// 临时性问题，稍后重试通常可以恢复
+    Warning,
+    /// 仅影响当前视频，批量任务可继续
+    Error,
+    /// 账号级问题，继续发送没有意义，应中止批量任务
+    Fatal,
+}
+
+/// B站错误码信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorCodeInfo {
+    pub code: i32,
+    pub message: &'static str,
+    pub message_en: &'static str,
+    pub retryable: bool,
+    pub severity: ErrorSeverity,
+}
+
+impl ErrorCodeInfo {
+    /// 当前语言下的错误信息
+    pub fn localized_message(&self) -> &'static str {
+        match i18n::current_locale() {
+            Locale::ZhCn => self.message,
+            Locale::En => self.message_en,
+        }
+    }
+}
+
+const fn entry(
+    code: i32,
+    message: &'static str,
+    message_en: &'static str,
+    retryable: bool,
+    severity: ErrorSeverity,
+) -> ErrorCodeInfo {
+    ErrorCodeInfo {
+        code,
+        message,
+        message_en,
+        retryable,
+        severity,
+    }
+}
+
+use ErrorSeverity::{Error, Fatal, Warning};
+
+/// 已知错误码表 (x/v2/reply/add 及相关接口)
+pub static ERROR_CODES: &[ErrorCodeInfo] = &[
+    // 通用错误码
+    entry(-101, "账号未登录", "Not logged in", false, Fatal),
+    entry(
+        -102,
+        "账号已被封停",
+        "Account has been banned",
+        false,
+        Fatal,
+    ),
+    entry(-111, "csrf校验失败", "CSRF check failed", false, Fatal),
+    entry(
+        -352,
+        "请求被风控校验拦截，请稍后重试",
+        "Blocked by risk control, please retry later",
+        true,
+        Warning,
+    ),
+    entry(-400, "请求错误", "Bad request", false, Error),
+    entry(-403, "访问权限不足", "Permission denied", false, Error),
+    entry(-404, "视频不存在", "Video not found", false, Error),
+    entry(
+        -412,
+        "请求被拦截，请稍后重试",
+        "Request intercepted, please retry later",
+        true,
+        Warning,
+    ),
+    entry(
+        -500,
+        "B站服务器错误",
+        "Bilibili server error",
+        true,
+        Warning,
+    ),
+    entry(
+        -503,
+        "B站服务繁忙，请稍后重试",
+        "Bilibili service busy, please retry later",
+        true,
+        Warning,
+    ),
+    entry(
+        -509,
+        "请求过于频繁，请稍后重试",
+        "Too many requests, please retry later",
+        true,
+        Warning,
+    ),
+    // 评论相关错误码
+    entry(
+        12001,
+        "评论主题已存在",
+        "Comment subject already exists",
+        false,
+        Error,
+    ),
+    entry(
+        12002,
+        "评论内容包含敏感词",
+        "Comment contains sensitive words",
+        false,
+        Error,
+    ),
+    entry(
+        12003,
+        "该视频禁止回复",
+        "Replies are disabled for this video",
+        false,
+        Error,
+    ),
+    entry(12006, "评论不存在", "Comment not found", false, Error),
+    entry(
+        12009,
+        "评论发送太频繁",
+        "Commenting too frequently",
+        true,
+        Warning,
+    ),
+    entry(12015, "需要输入验证码", "Captcha required", false, Fatal),
+    entry(
+        12016,
+        "您的账号存在异常，请完成验证后重试",
+        "Your account needs verification, please verify and retry",
+        false,
+        Fatal,
+    ),
+    entry(
+        12025,
+        "评论区已关闭",
+        "Comment section is closed",
+        false,
+        Error,
+    ),
+    entry(
+        12035,
+        "该账号已被UP主列入评论黑名单",
+        "This account is on the uploader's comment blacklist",
+        false,
+        Error,
+    ),
+    entry(
+        12051,
+        "重复评论，请勿刷屏",
+        "Duplicate comment, please don't spam",
+        false,
+        Error,
+    ),
+    entry(
+        12052,
+        "评论区已关闭",
+        "Comment section is closed",
+        false,
+        Error,
+    ),
+    entry(
+        12053,
+        "UP主设置了评论权限，当前账号无法评论",
+        "The uploader restricted who can comment; this account cannot comment",
+        false,
+        Error,
+    ),
+];
+
+/// 查询错误码信息
+pub fn lookup(code: i32) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES.iter().find(|info| info.code == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_logged_in_is_fatal() {
+        let info = lookup(-101).expect("-101 应在错误码表中");
+        assert_eq!(info.severity, Fatal);
+        assert!(!info.retryable);
+    }
+
+    #[test]
+    fn rate_limits_are_retryable_warnings() {
+        for code in [-352, -412, -500, -503, -509, 12009] {
+            let info = lookup(code).unwrap_or_else(|| panic!("{} 应在错误码表中", code));
+            assert!(info.retryable, "{} 应可重试", code);
+            assert_eq!(info.severity, Warning, "{}", code);
+        }
+    }
+
+    #[test]
+    fn video_level_errors_are_not_retryable() {
+        let info = lookup(12002).unwrap();
+        assert_eq!(info.severity, Error);
+        assert!(!info.retryable);
+    }
+
+    #[test]
+    fn unknown_code() {
+        assert!(lookup(123456).is_none());
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        for (i, info) in ERROR_CODES.iter().enumerate() {
+            assert!(
+                ERROR_CODES[..i].iter().all(|other| other.code != info.code),
+                "重复的错误码 {}",
+                info.code
+            );
+        }
+    }
+
+    #[test]
+    fn fatal_codes_are_not_retryable() {
+        for info in ERROR_CODES.iter().filter(|i| i.severity == Fatal) {
+            assert!(!info.retryable, "{}", info.code);
+        }
+    }
+}
@@ -5,6 +5,7 @@ use reqwest::Response;
 use serde::Deserialize;
 
 use super::{BiliApiError, BILI_CLIENT};
+use crate::i18n::{self, Msg};
 use crate::models::{LoginCredential, LoginPollResult, LoginStatus, QrCodeData, UserInfo};
 
 const QRCODE_GENERATE_URL: &str =
@@ -140,11 +141,11 @@ pub async fn poll_qrcode_status(qrcode_key: &str) -> Result<LoginPollResult, Bil
                     log::error!("保存凭证失败: {}", e);
                 }
             }
-            (LoginStatus::Confirmed, "登录成功".to_string())
+            (LoginStatus::Confirmed, i18n::t(Msg::LoginConfirmed).to_string())
         }
-        86038 => (LoginStatus::Expired, "二维码已过期".to_string()),
-        86090 => (LoginStatus::Scanned, "已扫码，请在手机上确认".to_string()),
-        86101 => (LoginStatus::Waiting, "等待扫码".to_string()),
+        86038 => (LoginStatus::Expired, i18n::t(Msg::LoginQrExpired).to_string()),
+        86090 => (LoginStatus::Scanned, i18n::t(Msg::LoginScanned).to_string()),
+        86101 => (LoginStatus::Waiting, i18n::t(Msg::LoginWaiting).to_string()),
         _ => (LoginStatus::Error, data.message),
     };
 
@@ -238,7 +239,14 @@ pub fn logout() {
 
 /// 初始化时加载已保存的凭证
 pub fn init_credential() {
-    if let Ok(Some(credential)) = crate::storage::credential::load_credential() {
+    let credential = match crate::storage::credential::load_credential() {
+        Ok(credential) => credential,
+        Err(e) => {
+            crate::storage::db::report_issue("credential", &e);
+            return;
+        }
+    };
+    if let Some(credential) = credential {
         // 检查是否过期
         if credential.expires_at > chrono::Utc::now().timestamp() {
             BILI_CLIENT.set_credential(Some(credential));
@@ -5,6 +5,10 @@ pub mod login;
 pub mod search;
 pub mod comment;
 pub mod error;
+pub mod error_code;
+pub mod sanitize;
+pub mod video;
 
 pub use client::BILI_CLIENT;
 pub use error::BiliApiError;
+pub use error_code::ErrorSeverity;
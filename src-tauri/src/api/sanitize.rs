@@ -0,0 +1,190 @@
+// 搜索结果文本清理: 去除 HTML 标签、解码实体，并记录关键词高亮位置
+
+use crate::models::HighlightRange;
+
+/// 清理后的文本
+#[derive(Debug, Clone, Default)]
+pub struct SanitizedText {
+    pub text: String,
+    /// 高亮 (`<em>`) 片段在清理后文本中的位置
+    pub highlights: Vec<HighlightRange>,
+}
+
+/// 解码单个 HTML 实体 (不含 `&` 和 `;`)
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(num) = entity.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        "ldquo" => Some('“'),
+        "rdquo" => Some('”'),
+        "lsquo" => Some('‘'),
+        "rsquo" => Some('’'),
+        "hellip" => Some('…'),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "middot" => Some('·'),
+        "times" => Some('×'),
+        "yen" => Some('¥'),
+        "copy" => Some('©'),
+        "reg" => Some('®'),
+        _ => None,
+    }
+}
+
+/// 追加字符并更新 UTF-16 位置
+fn push(text: &mut String, c: char, offset: &mut usize) {
+    text.push(c);
+    *offset += c.len_utf16();
+}
+
+/// `<` 之后是否像一个标签 (字母、`/` 或 `!`)，否则按普通文本处理 (如 "a < b")
+fn starts_tag(rest: &str) -> bool {
+    rest[1..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// 实体最长长度 (超过则视为普通文本)
+const MAX_ENTITY_LEN: usize = 10;
+
+/// 去除所有 HTML 标签并解码实体，`<em>` 包裹的片段记录为高亮
+///
+/// 高亮位置按 UTF-16 编码单元计算，与前端字符串下标一致；
+/// 无法识别的实体和未闭合的 `<` 按原文保留
+pub fn sanitize(html: &str) -> SanitizedText {
+    let mut text = String::with_capacity(html.len());
+    let mut highlights = Vec::new();
+    // 当前输出位置 (UTF-16) 和未闭合高亮的起点
+    let mut offset = 0usize;
+    let mut highlight_start: Option<usize> = None;
+
+    let mut rest = html;
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => {
+                let Some(end) = rest.find('>').filter(|_| starts_tag(rest)) else {
+                    push(&mut text, c, &mut offset);
+                    rest = &rest[1..];
+                    continue;
+                };
+                let tag = rest[1..end].trim();
+                let name = tag
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or("")
+                    .to_ascii_lowercase();
+
+                if name == "em" {
+                    if tag.starts_with('/') {
+                        if let Some(start) = highlight_start.take() {
+                            if offset > start {
+                                highlights.push(HighlightRange { start, end: offset });
+                            }
+                        }
+                    } else if highlight_start.is_none() {
+                        highlight_start = Some(offset);
+                    }
+                }
+                rest = &rest[end + 1..];
+            }
+            '&' => {
+                let decoded = rest[1..]
+                    .find(';')
+                    .filter(|&end| end <= MAX_ENTITY_LEN)
+                    .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (c, end + 2)));
+                match decoded {
+                    Some((decoded, len)) => {
+                        push(&mut text, decoded, &mut offset);
+                        rest = &rest[len..];
+                    }
+                    None => {
+                        push(&mut text, c, &mut offset);
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            _ => {
+                push(&mut text, c, &mut offset);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    // 未闭合的高亮延续到文本末尾
+    if let Some(start) = highlight_start {
+        if offset > start {
+            highlights.push(HighlightRange { start, end: offset });
+        }
+    }
+
+    SanitizedText { text, highlights }
+}
+
+/// 只返回清理后的文本
+pub fn sanitize_text(html: &str) -> String {
+    sanitize(html).text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: usize, end: usize) -> HighlightRange {
+        HighlightRange { start, end }
+    }
+
+    #[test]
+    fn strips_tags_and_records_highlights() {
+        let result = sanitize("<em class=\"keyword\">原神</em>攻略<br/><b>合集</b>");
+        assert_eq!(result.text, "原神攻略合集");
+        assert_eq!(result.highlights, vec![range(0, 2)]);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            sanitize_text("Tom &amp; Jerry &lt;3 &#65;&#x42; &quot;ok&quot;"),
+            "Tom & Jerry <3 AB \"ok\""
+        );
+        // 无法识别或过长的实体、末尾孤立的 `&` 按原文保留
+        assert_eq!(
+            sanitize_text("&unknown; &verylongentity; &"),
+            "&unknown; &verylongentity; &"
+        );
+    }
+
+    #[test]
+    fn keeps_stray_angle_brackets() {
+        let result = sanitize("a < b <3 <em>c</em>");
+        assert_eq!(result.text, "a < b <3 c");
+        assert_eq!(result.highlights, vec![range(9, 10)]);
+    }
+
+    #[test]
+    fn highlight_offsets_are_utf16() {
+        // 😀 占两个 UTF-16 编码单元
+        let result = sanitize("😀<em>测试</em>一下<em>😀</em>");
+        assert_eq!(result.text, "😀测试一下😀");
+        assert_eq!(result.highlights, vec![range(2, 4), range(6, 8)]);
+    }
+
+    #[test]
+    fn handles_unclosed_and_empty_highlights() {
+        assert_eq!(sanitize("<em>abc").highlights, vec![range(0, 3)]);
+        assert!(sanitize("<em></em>x").highlights.is_empty());
+    }
+}
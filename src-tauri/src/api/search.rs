@@ -4,12 +4,18 @@ use serde_json::Value;
 use std::time::Duration;
 use tokio::time::sleep;
 
+use super::sanitize::{sanitize, sanitize_text};
 use super::{BiliApiError, BILI_CLIENT};
-use crate::models::{SearchResult, VideoItem};
+use crate::models::{DurationFilter, SearchFilters, SearchOrder, SearchResult, VideoItem};
+use crate::storage::settings;
 
 const SEARCH_URL: &str = "https://api.bilibili.com/x/web-interface/search/type";
-const MAX_RETRIES: u32 = 5;
-const RETRY_DELAY_MS: u64 = 500;
+
+/// 搜索接口最多返回的页数
+pub const MAX_SEARCH_PAGES: u32 = 50;
+
+/// 搜索接口单页最多返回的结果数
+pub const MAX_PAGE_SIZE: u32 = 50;
 
 /// B站 API 响应结构
 #[derive(Debug, Deserialize)]
@@ -71,49 +77,83 @@ fn value_to_u64(v: &Value) -> u64 {
     }
 }
 
+/// 筛选条件对应的查询参数 (未设置的条件不传)
+fn filter_params(filters: &SearchFilters) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+
+    if filters.duration != DurationFilter::All {
+        params.push(("duration", filters.duration.query_value().to_string()));
+    }
+
+    if let Some(tid) = filters.tid.filter(|&tid| tid > 0) {
+        params.push(("tids", tid.to_string()));
+    }
+
+    // 发布时间需要同时传入起止时间，只设置一端时另一端不限
+    if filters.pubtime_begin.is_some() || filters.pubtime_end.is_some() {
+        let begin = filters.pubtime_begin.unwrap_or(0);
+        let end = filters
+            .pubtime_end
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+        params.push(("pubtime_begin_s", begin.to_string()));
+        params.push(("pubtime_end_s", end.to_string()));
+    }
+
+    params
+}
+
 /// 搜索视频
 pub async fn search_videos(
     keyword: &str,
     page: u32,
     page_size: u32,
-    order: Option<&str>,
+    order: SearchOrder,
+    filters: &SearchFilters,
 ) -> Result<SearchResult, BiliApiError> {
     let client = &BILI_CLIENT;
-    let order = order.unwrap_or("totalrank");
 
     log::info!(
-        "🔍 搜索视频: keyword={}, page={}, page_size={}, order={}",
+        "🔍 搜索视频: keyword={}, page={}, page_size={}, order={:?}, filters={:?}",
         keyword,
         page,
         page_size,
-        order
+        order,
+        filters
     );
 
+    let mut query = vec![
+        ("search_type", "video".to_string()),
+        ("keyword", keyword.to_string()),
+        ("page", page.to_string()),
+        ("page_size", page_size.to_string()),
+        ("order", order.query_value().to_string()),
+    ];
+    query.extend(filter_params(filters));
+
+    // 重试次数和间隔在每次搜索时从设置读取
+    let settings = settings::current();
+    let max_attempts = settings.search_max_attempts.max(1);
+    let retry_delay_ms = settings.search_retry_delay_ms;
+
     let mut last_error = None;
 
-    for attempt in 1..=MAX_RETRIES {
+    for attempt in 1..=max_attempts {
         let headers = client.build_headers();
 
         let response: Response = match client
             .client()
             .get(SEARCH_URL)
             .headers(headers)
-            .query(&[
-                ("search_type", "video"),
-                ("keyword", keyword),
-                ("page", &page.to_string()),
-                ("page_size", &page_size.to_string()),
-                ("order", order),
-            ])
+            .query(&query)
             .send()
             .await
         {
             Ok(resp) => resp,
             Err(e) => {
-                log::error!("❌ 搜索网络请求失败 (尝试 {}/{}): {}", attempt, MAX_RETRIES, e);
+                log::error!("❌ 搜索网络请求失败 (尝试 {}/{}): {}", attempt, max_attempts, e);
                 last_error = Some(BiliApiError::from(e));
-                if attempt < MAX_RETRIES {
-                    sleep(Duration::from_millis(RETRY_DELAY_MS)).await;
+                if attempt < max_attempts {
+                    sleep(Duration::from_millis(retry_delay_ms)).await;
                 }
                 continue;
             }
@@ -123,10 +163,10 @@ pub async fn search_videos(
         let response_text = match response.text().await {
             Ok(text) => text,
             Err(e) => {
-                log::error!("❌ 获取响应文本失败 (尝试 {}/{}): {}", attempt, MAX_RETRIES, e);
+                log::error!("❌ 获取响应文本失败 (尝试 {}/{}): {}", attempt, max_attempts, e);
                 last_error = Some(BiliApiError::ParseError(format!("获取响应文本失败: {}", e)));
-                if attempt < MAX_RETRIES {
-                    sleep(Duration::from_millis(RETRY_DELAY_MS)).await;
+                if attempt < max_attempts {
+                    sleep(Duration::from_millis(retry_delay_ms)).await;
                 }
                 continue;
             }
@@ -134,10 +174,10 @@ pub async fn search_videos(
 
         // 检查是否返回了 HTML (风控页面)
         if response_text.trim_start().starts_with("<!DOCTYPE") || response_text.trim_start().starts_with("<html") {
-            log::warn!("⚠️ B站返回了HTML页面 (风控/验证), 重试 ({}/{})", attempt, MAX_RETRIES);
+            log::warn!("⚠️ B站返回了HTML页面 (风控/验证), 重试 ({}/{})", attempt, max_attempts);
             last_error = Some(BiliApiError::ParseError("B站返回了验证页面，请稍后重试".to_string()));
-            if attempt < MAX_RETRIES {
-                sleep(Duration::from_millis(RETRY_DELAY_MS * 2)).await; // HTML 情况等待更久
+            if attempt < max_attempts {
+                sleep(Duration::from_millis(retry_delay_ms * 2)).await; // HTML 情况等待更久
             }
             continue;
         }
@@ -146,11 +186,11 @@ pub async fn search_videos(
         let resp: BiliResponse<SearchData> = match serde_json::from_str(&response_text) {
             Ok(r) => r,
             Err(e) => {
-                log::error!("❌ 搜索响应解析失败 (尝试 {}/{}): {}", attempt, MAX_RETRIES, e);
+                log::error!("❌ 搜索响应解析失败 (尝试 {}/{}): {}", attempt, max_attempts, e);
                 log::error!("📄 原始响应 (前300字符): {}", &response_text[..response_text.len().min(300)]);
                 last_error = Some(BiliApiError::ParseError(format!("响应解析失败: {}", e)));
-                if attempt < MAX_RETRIES {
-                    sleep(Duration::from_millis(RETRY_DELAY_MS)).await;
+                if attempt < max_attempts {
+                    sleep(Duration::from_millis(retry_delay_ms)).await;
                 }
                 continue;
             }
@@ -170,18 +210,22 @@ pub async fn search_videos(
             .result
             .unwrap_or_default()
             .into_iter()
-            .map(|item| VideoItem {
-                aid: item.aid,
-                bvid: item.bvid,
-                title: clean_html_tags(&item.title),
-                author: item.author,
-                mid: item.mid,
-                pic: normalize_pic_url(&item.pic),
-                play: value_to_u64(&item.play),
-                danmaku: value_to_u64(&item.danmaku),
-                pubdate: item.pubdate,
-                duration: item.duration,
-                description: item.description,
+            .map(|item| {
+                let title = sanitize(&item.title);
+                VideoItem {
+                    aid: item.aid,
+                    bvid: item.bvid,
+                    title: title.text,
+                    author: sanitize_text(&item.author),
+                    mid: item.mid,
+                    pic: normalize_pic_url(&item.pic),
+                    play: value_to_u64(&item.play),
+                    danmaku: value_to_u64(&item.danmaku),
+                    pubdate: item.pubdate,
+                    duration: item.duration,
+                    description: sanitize_text(&item.description),
+                    title_highlights: title.highlights,
+                }
             })
             .collect();
 
@@ -205,12 +249,6 @@ pub async fn search_videos(
     Err(last_error.unwrap_or_else(|| BiliApiError::Other("搜索失败".to_string())))
 }
 
-/// 清理 HTML 标签 (搜索结果中的高亮标签)
-fn clean_html_tags(text: &str) -> String {
-    text.replace("<em class=\"keyword\">", "")
-        .replace("</em>", "")
-}
-
 /// 标准化图片 URL
 fn normalize_pic_url(url: &str) -> String {
     if url.starts_with("//") {
@@ -0,0 +1,128 @@
+use reqwest::Response;
+use serde::Deserialize;
+
+use super::{BiliApiError, BILI_CLIENT};
+use crate::models::VideoItem;
+use crate::utils::bvid::av_to_bv;
+
+const VIDEO_VIEW_URL: &str = "https://api.bilibili.com/x/web-interface/view";
+
+/// B站 API 响应结构
+#[derive(Debug, Deserialize)]
+struct BiliResponse<T> {
+    code: i32,
+    #[serde(default)]
+    message: String,
+    data: Option<T>,
+}
+
+/// 视频详情响应数据
+#[derive(Debug, Deserialize)]
+struct ViewData {
+    aid: u64,
+    #[serde(default)]
+    bvid: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    pic: String,
+    #[serde(default)]
+    pubdate: i64,
+    #[serde(default)]
+    desc: String,
+    /// 时长 (秒)
+    #[serde(default)]
+    duration: u64,
+    owner: ViewOwner,
+    #[serde(default)]
+    stat: ViewStat,
+}
+
+/// UP主信息
+#[derive(Debug, Deserialize)]
+struct ViewOwner {
+    mid: u64,
+    #[serde(default)]
+    name: String,
+}
+
+/// 视频统计数据
+#[derive(Debug, Default, Deserialize)]
+struct ViewStat {
+    #[serde(default)]
+    view: u64,
+    #[serde(default)]
+    danmaku: u64,
+}
+
+/// 时长秒数格式化为与搜索结果一致的 `"mm:ss"`
+fn format_duration(secs: u64) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// 获取视频信息
+pub async fn get_video(aid: u64) -> Result<VideoItem, BiliApiError> {
+    let client = &BILI_CLIENT;
+    let headers = client.build_headers();
+
+    log::info!("🎬 获取视频信息: aid={}", aid);
+
+    let response: Response = client
+        .client()
+        .get(VIDEO_VIEW_URL)
+        .headers(headers)
+        .query(&[("aid", aid.to_string())])
+        .send()
+        .await?;
+
+    let resp: BiliResponse<ViewData> = response.json().await?;
+
+    if resp.code != 0 {
+        log::warn!(
+            "⚠️ 获取视频信息失败: aid={}, code={}, message={}",
+            aid,
+            resp.code,
+            resp.message
+        );
+        return Err(BiliApiError::ApiError {
+            code: resp.code,
+            message: resp.message,
+        });
+    }
+
+    let data = resp
+        .data
+        .ok_or_else(|| BiliApiError::ParseError("缺少数据".to_string()))?;
+    let bvid = if data.bvid.is_empty() {
+        av_to_bv(data.aid).unwrap_or_default()
+    } else {
+        data.bvid
+    };
+
+    Ok(VideoItem {
+        aid: data.aid,
+        bvid,
+        title: data.title,
+        author: data.owner.name,
+        mid: data.owner.mid,
+        pic: data.pic,
+        play: data.stat.view,
+        danmaku: data.stat.danmaku,
+        pubdate: data.pubdate,
+        duration: format_duration(data.duration),
+        description: data.desc,
+        title_highlights: Vec::new(),
+    })
+}
+
+/// 请求短链接并跟随跳转，返回最终地址
+pub async fn resolve_short_link(url: &str) -> Result<String, BiliApiError> {
+    let client = &BILI_CLIENT;
+    let headers = client.build_headers();
+
+    let response: Response = client.client().get(url).headers(headers).send().await?;
+    let target = response.url().to_string();
+
+    log::info!("🔗 短链接跳转: {} -> {}", url, target);
+    Ok(target)
+}
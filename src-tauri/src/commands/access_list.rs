@@ -0,0 +1,109 @@
+use crate::i18n::{self, Msg};
+use crate::models::{AccessListEntry, AccessListKind, AccessListTarget, VideoItem};
+use crate::storage::{self, access_list, settings};
+
+/// 规范化名单条目的值: UP主为 mid，视频为 aid (可带 av 前缀) 或 bvid
+fn normalize_value(target: AccessListTarget, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let invalid = || i18n::tf(Msg::InvalidAccessListValue, &[&value]);
+
+    match target {
+        AccessListTarget::Author => value
+            .parse::<u64>()
+            .map(|mid| mid.to_string())
+            .map_err(|_| invalid()),
+        AccessListTarget::Video => {
+            let lower = value.to_ascii_lowercase();
+            if let Some(rest) = lower.strip_prefix("bv") {
+                if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(invalid());
+                }
+                // bvid 除前缀外区分大小写
+                return Ok(format!("BV{}", &value[2..]));
+            }
+            lower
+                .strip_prefix("av")
+                .unwrap_or(&lower)
+                .parse::<u64>()
+                .map(|aid| aid.to_string())
+                .map_err(|_| invalid())
+        }
+    }
+}
+
+/// 名单条目的备注 (为空时显示 "-")
+fn reason_of(entry: &AccessListEntry) -> &str {
+    entry.reason.as_deref().unwrap_or("-")
+}
+
+/// 检查视频是否允许评论: 命中屏蔽名单，或在仅允许名单模式下不在允许名单中时返回原因
+pub(super) fn check_video_access(video: &VideoItem) -> Result<(), String> {
+    let blocked = access_list::find_matches(AccessListKind::Blocklist, video)?;
+    if let Some(entry) = blocked.first() {
+        let msg = match entry.target {
+            AccessListTarget::Author => Msg::SkipAuthorBlocked,
+            AccessListTarget::Video => Msg::SkipVideoBlocked,
+        };
+        return Err(i18n::tf(msg, &[&reason_of(entry)]));
+    }
+
+    if settings::current().allowlist_only
+        && access_list::find_matches(AccessListKind::Allowlist, video)?.is_empty()
+    {
+        return Err(i18n::t(Msg::SkipNotAllowlisted).to_string());
+    }
+
+    Ok(())
+}
+
+/// 获取名单条目 (可按名单类型筛选)
+#[tauri::command]
+pub fn get_access_list(kind: Option<AccessListKind>) -> Result<Vec<AccessListEntry>, String> {
+    access_list::load_entries(kind)
+}
+
+/// 添加名单条目 (已存在时更新备注)，返回保存后的条目
+#[tauri::command]
+pub fn add_access_list_entry(
+    kind: AccessListKind,
+    target: AccessListTarget,
+    value: String,
+    reason: Option<String>,
+) -> Result<AccessListEntry, String> {
+    let now = chrono::Utc::now().timestamp();
+    let entry = AccessListEntry {
+        kind,
+        target,
+        value: normalize_value(target, &value)?,
+        reason: reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty()),
+        created_at: now,
+        updated_at: now,
+    };
+
+    let _guard = storage::lock();
+    let entry = access_list::save_entry(&entry)?;
+    log::info!(
+        "名单已更新: {:?} {:?} {}",
+        entry.kind,
+        entry.target,
+        entry.value
+    );
+    Ok(entry)
+}
+
+/// 删除名单条目
+#[tauri::command]
+pub fn remove_access_list_entry(
+    kind: AccessListKind,
+    target: AccessListTarget,
+    value: String,
+) -> Result<(), String> {
+    let value = normalize_value(target, &value)?;
+    if access_list::remove_entry(kind, target, &value)? {
+        Ok(())
+    } else {
+        Err(i18n::t(Msg::AccessListEntryNotFound).to_string())
+    }
+}
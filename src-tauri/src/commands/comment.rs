@@ -1,13 +1,33 @@
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
 use uuid::Uuid;
 
-use crate::api::comment;
-use crate::models::{BatchStatus, CommentResult, CommentTask, TaskStatus, VideoItem};
+use crate::api::{comment, login};
+use crate::api::error_code::{self, ErrorCodeInfo};
+use crate::api::{BiliApiError, ErrorSeverity};
+use crate::i18n::{self, Msg};
+use crate::moderation;
+use crate::models::{
+    BatchSchedule, BatchState, BatchStatus, CommentLedgerEntry, CommentResult, CommentTask,
+    TaskStatus, UserInfo, VideoItem,
+};
+use crate::render::{self, RenderContext, RenderError};
+use crate::storage::{self, batch as batch_storage, ledger, settings};
+use crate::storage::template as template_storage;
+
+use super::access_list::check_video_access;
+use super::filter::filter_videos;
+
+/// 等待排期时检查取消标记的间隔 (秒)
+const SCHEDULE_POLL_SECS: i64 = 30;
 
 /// 批量任务存储
-static BATCH_TASKS: once_cell::sync::Lazy<Arc<Mutex<HashMap<String, BatchStatus>>>> =
+pub(super) static BATCH_TASKS: once_cell::sync::Lazy<Arc<Mutex<HashMap<String, BatchStatus>>>> =
     once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
 /// 取消标记存储
@@ -24,169 +44,800 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
-/// 发送单条评论
+/// 发送单条评论 (与批量发送一样受屏蔽名单和仅允许名单模式约束)
 #[tauri::command]
-pub async fn send_comment(_bvid: String, aid: u64, content: String) -> Result<CommentResult, String> {
-    log::info!("📝 Command: 发送单条评论 aid={}", aid);
-    comment::send_comment(aid, &content)
+pub async fn send_comment(video: VideoItem, content: String) -> Result<CommentResult, String> {
+    log::info!("{}", i18n::tf(Msg::LogSendComment, &[&video.aid]));
+    check_video_access(&video)?;
+
+    let result = comment::send_comment(video.aid, &content)
         .await
-        .map_err(|e| e.to_user_message())
+        .map_err(|e| e.to_user_message())?;
+
+    if result.success {
+        let entry = CommentLedgerEntry {
+            aid: video.aid,
+            bvid: video.bvid,
+            rpid: result.rpid,
+            content,
+            batch_id: None,
+            template_id: None,
+            sent_at: chrono::Utc::now().timestamp(),
+        };
+        if let Err(e) = ledger::record_comment(&entry) {
+            log::error!("写入已发送评论记录失败: {}", e);
+        }
+    }
+
+    Ok(result)
 }
 
 /// 批量发送评论 (启动异步任务)
+///
+/// `content` 为评论模板，执行时按视频渲染占位符；
+/// `strict_template` 为 true 时未知占位符会导致该任务被跳过；
+/// `dry_run` 为 true 时执行完整的预检流程，但不会真正发送评论；
+/// `schedule` 可指定开始时间和允许执行的时间段；
+/// `template_id` 为评论内容来源的模板，用于记录使用情况；
+/// `filter_preset_id` 指定时先按筛选预设排除不符合规则的视频
 #[tauri::command]
-pub async fn batch_send_comments(videos: Vec<VideoItem>, content: String) -> Result<String, String> {
-    let batch_id = Uuid::new_v4().to_string();
+pub async fn batch_send_comments(
+    videos: Vec<VideoItem>,
+    content: String,
+    dry_run: Option<bool>,
+    schedule: Option<BatchSchedule>,
+    strict_template: Option<bool>,
+    template_id: Option<String>,
+    filter_preset_id: Option<String>,
+) -> Result<String, String> {
+    if let Some(schedule) = &schedule {
+        schedule.validate()?;
+    }
+    let videos = filter_videos(videos, filter_preset_id.as_deref())?;
+
+    let mut batch = new_batch(&videos, &content, BatchState::Running, None);
+    batch.schedule = schedule;
+    batch.strict_template = strict_template.unwrap_or(false);
+    attach_template(&mut batch, template_id);
+    let batch_id = batch.batch_id.clone();
 
     log::info!(
-        "🚀 Command: 启动批量评论任务 batch_id={}, 视频数={}",
-        batch_id,
-        videos.len()
+        "{}",
+        i18n::tf(Msg::LogBatchStart, &[&batch_id, &videos.len()])
     );
 
+    // 持有存储锁加入并启动批次，与恢复备份互斥
+    {
+        let _guard = storage::lock();
+        BATCH_TASKS.lock().insert(batch_id.clone(), batch);
+        start_batch(&batch_id, dry_run.unwrap_or(false));
+    }
+
+    Ok(batch_id)
+}
+
+/// 创建批次 (尚未加入存储)
+pub(super) fn new_batch(
+    videos: &[VideoItem],
+    content: &str,
+    state: BatchState,
+    created_by: Option<String>,
+) -> BatchStatus {
+    let now = chrono::Utc::now().timestamp();
+
     // 创建任务列表
     let tasks: Vec<CommentTask> = videos
         .iter()
         .map(|video| CommentTask {
             id: Uuid::new_v4().to_string(),
             video: video.clone(),
-            content: content.clone(),
+            content: content.to_string(),
             status: TaskStatus::Pending,
             error_msg: None,
-            created_at: chrono::Utc::now().timestamp(),
+            error_code: None,
+            review_note: None,
+            rendered: false,
+            rpid: None,
+            created_at: now,
             completed_at: None,
         })
         .collect();
 
-    let batch_status = BatchStatus {
-        batch_id: batch_id.clone(),
+    BatchStatus {
+        batch_id: Uuid::new_v4().to_string(),
         total: tasks.len(),
         completed: 0,
         success: 0,
         failed: 0,
+        skipped: 0,
+        dry_run: false,
+        state,
+        created_by,
+        approved_by: None,
+        approved_at: None,
+        created_at: now,
+        schedule: None,
+        next_run_at: None,
+        template: content.to_string(),
+        strict_template: false,
+        template_id: None,
+        template_revision: None,
         tasks,
+    }
+}
+
+/// 记录批次使用的模板及其修订版本 (失败只记录日志)
+///
+/// 批次内容与模板当前版本不一致时 (如界面中未保存的修改) 不记录修订版本
+pub(super) fn attach_template(batch: &mut BatchStatus, template_id: Option<String>) {
+    if let Some(id) = &template_id {
+        match template_storage::mark_template_used(id) {
+            Ok(Some((revision, content))) if content == batch.template => {
+                batch.template_revision = Some(revision);
+            }
+            Ok(Some(_)) => log::info!("批次内容与模板 {} 的当前版本不一致，不记录修订版本", id),
+            Ok(None) => {}
+            Err(e) => log::warn!("记录模板使用时间失败: {}", e),
+        }
+    }
+    batch.template_id = template_id;
+}
+
+/// 模板用到用户信息时获取当前登录用户
+pub(super) async fn fetch_user_for(template: &str) -> Option<UserInfo> {
+    if !render::needs_user(template) {
+        return None;
+    }
+    match login::get_user_info().await {
+        Ok(user) => user,
+        Err(e) => {
+            log::warn!("获取用户信息失败，用户相关占位符将无法渲染: {}", e);
+            None
+        }
+    }
+}
+
+/// 按任务对应的视频渲染评论内容
+pub(super) fn render_task(
+    task: &mut CommentTask,
+    template: &str,
+    user: Option<&UserInfo>,
+    strict: bool,
+) -> Result<(), RenderError> {
+    let ctx = RenderContext {
+        video: &task.video,
+        user,
     };
+    task.content = render::render(template, &ctx, strict)?;
+    task.rendered = true;
+    Ok(())
+}
 
-    // 保存批次状态
+/// 将批次切换为执行中 (有未到的开始时间时为已排期)
+///
+/// 调用方需在检查批次状态的同一次加锁中调用，避免同一批次被重复启动
+pub(super) fn mark_started(batch: &mut BatchStatus, dry_run: bool) {
+    let start_at = batch.schedule.as_ref().and_then(|s| s.start_at_utc());
+    match start_at {
+        Some(start_at) if start_at > Utc::now() => {
+            log::info!(
+                "{}",
+                i18n::tf(
+                    Msg::LogBatchScheduled,
+                    &[&batch.batch_id, &start_at.to_rfc3339()]
+                )
+            );
+            batch.state = BatchState::Scheduled;
+            batch.next_run_at = Some(start_at.timestamp());
+        }
+        _ => batch.state = BatchState::Running,
+    }
+    batch.dry_run = dry_run;
+}
+
+/// 启动已加入存储的批次
+pub(super) fn start_batch(batch_id: &str, dry_run: bool) {
     {
         let mut batches = BATCH_TASKS.lock();
-        batches.insert(batch_id.clone(), batch_status);
+        if let Some(batch) = batches.get_mut(batch_id) {
+            mark_started(batch, dry_run);
+        }
     }
+    spawn_batch(batch_id);
+}
+
+/// 保存批次并启动后台执行 (批次须已通过 `mark_started` 切换状态)
+pub(super) fn spawn_batch(batch_id: &str) {
+    persist_batch(batch_id);
 
     // 初始化取消标记
     {
         let mut flags = CANCEL_FLAGS.lock();
-        flags.insert(batch_id.clone(), false);
+        flags.insert(batch_id.to_string(), false);
     }
 
     // 启动异步执行任务
-    let batch_id_clone = batch_id.clone();
-    tokio::spawn(async move {
-        execute_batch_tasks(batch_id_clone).await;
+    let batch_id = batch_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        execute_batch_tasks(batch_id).await;
     });
+}
 
-    Ok(batch_id)
+/// 保存批次及其全部任务 (失败只记录日志，不影响任务执行)
+pub(super) fn persist_batch(batch_id: &str) {
+    // 持有存储锁再取快照，避免较旧的快照覆盖较新的
+    let _guard = storage::lock();
+    let Some(snapshot) = BATCH_TASKS.lock().get(batch_id).cloned() else {
+        return;
+    };
+    if let Err(e) = batch_storage::save_batch(&snapshot) {
+        log::error!("保存批次失败: {}", e);
+    }
+}
+
+/// 保存批次的状态和统计，以及第 `task` 个任务 (执行过程中调用，只写入变化的行)
+fn persist_progress(batch_id: &str, task: Option<usize>) {
+    let _guard = storage::lock();
+    let batches = BATCH_TASKS.lock();
+    let Some(batch) = batches.get(batch_id) else {
+        return;
+    };
+    if let Err(e) = batch_storage::save_progress(batch, task) {
+        log::error!("保存批次失败: {}", e);
+    }
+}
+
+/// 启动时加载已保存的批次
+pub fn init_batches() {
+    let mut loaded = match batch_storage::load_batches() {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            storage::db::report_issue("batches", &e);
+            return;
+        }
+    };
+    let now = chrono::Utc::now().timestamp();
+    let mut resume = Vec::new();
+    let mut cancelled = Vec::new();
+
+    for batch in loaded.iter_mut() {
+        // 已排期的批次重启后继续等待执行
+        let scheduled = batch.schedule.is_some()
+            && matches!(batch.state, BatchState::Scheduled | BatchState::Running);
+        if scheduled {
+            // 退出时正在发送的评论可能已经发出，标记为失败而不是重新发送，避免重复评论
+            for task in batch.tasks.iter_mut() {
+                if task.status == TaskStatus::Running {
+                    task.status = TaskStatus::Failed;
+                    task.error_msg = Some(i18n::t(Msg::TaskInterrupted).to_string());
+                    task.completed_at = Some(now);
+                    batch.failed += 1;
+                    batch.completed += 1;
+                }
+            }
+            resume.push((batch.batch_id.clone(), batch.dry_run));
+            continue;
+        }
+
+        // 上次退出时仍在执行的批次无法继续，剩余任务标记为取消
+        if batch.state == BatchState::Running {
+            for task in batch.tasks.iter_mut() {
+                if matches!(task.status, TaskStatus::Pending | TaskStatus::Running) {
+                    task.status = TaskStatus::Cancelled;
+                    task.completed_at = Some(now);
+                }
+            }
+            batch.state = BatchState::Cancelled;
+            cancelled.push(batch.batch_id.clone());
+        }
+    }
+
+    let count = loaded.len();
+    {
+        let mut batches = BATCH_TASKS.lock();
+        for batch in loaded {
+            batches.insert(batch.batch_id.clone(), batch);
+        }
+    }
+    log::info!("已加载 {} 个保存的批次", count);
+
+    for batch_id in cancelled {
+        persist_batch(&batch_id);
+    }
+
+    for (batch_id, dry_run) in resume {
+        log::info!("恢复已排期的批次: batch_id={}", batch_id);
+        start_batch(&batch_id, dry_run);
+    }
+}
+
+/// 批次历史: 已保存的批次，内存中的批次 (状态可能更新) 优先
+///
+/// 被 `clear_batch` 移出内存的批次仍会列出
+pub(super) fn batch_history() -> Result<Vec<BatchStatus>, String> {
+    let mut history: HashMap<String, BatchStatus> = batch_storage::load_batches()?
+        .into_iter()
+        .map(|b| (b.batch_id.clone(), b))
+        .collect();
+    for batch in BATCH_TASKS.lock().values() {
+        history.insert(batch.batch_id.clone(), batch.clone());
+    }
+    Ok(history.into_values().collect())
+}
+
+/// 是否有正在执行或已排期的批次
+pub fn has_active_batches() -> bool {
+    BATCH_TASKS
+        .lock()
+        .values()
+        .any(|b| matches!(b.state, BatchState::Running | BatchState::Scheduled))
+}
+
+/// 重新从存储加载批次，替换内存中的批次 (恢复备份后调用)
+pub fn reload_batches() {
+    BATCH_TASKS.lock().clear();
+    init_batches();
+}
+
+/// 批次是否已被取消
+fn is_cancelled(batch_id: &str) -> bool {
+    CANCEL_FLAGS.lock().get(batch_id).copied().unwrap_or(false)
+}
+
+/// 等待到指定时刻，期间定期检查取消标记；返回 false 表示等待中被取消
+async fn wait_until(batch_id: &str, deadline: DateTime<Utc>) -> bool {
+    {
+        let mut batches = BATCH_TASKS.lock();
+        if let Some(batch) = batches.get_mut(batch_id) {
+            batch.next_run_at = Some(deadline.timestamp());
+        }
+    }
+    persist_progress(batch_id, None);
+
+    loop {
+        if is_cancelled(batch_id) {
+            return false;
+        }
+        let remaining = (deadline - Utc::now()).num_seconds();
+        if remaining <= 0 {
+            break;
+        }
+        sleep(Duration::from_secs(remaining.min(SCHEDULE_POLL_SECS) as u64)).await;
+    }
+
+    let mut batches = BATCH_TASKS.lock();
+    if let Some(batch) = batches.get_mut(batch_id) {
+        batch.next_run_at = None;
+    }
+    true
 }
 
 /// 执行批量任务
 async fn execute_batch_tasks(batch_id: String) {
-    let task_count = {
+    let (task_count, dry_run, schedule, template, strict) = {
         let batches = BATCH_TASKS.lock();
-        batches.get(&batch_id).map(|b| b.tasks.len()).unwrap_or(0)
+        match batches.get(&batch_id) {
+            Some(b) => (
+                b.tasks.len(),
+                b.dry_run,
+                b.schedule.clone(),
+                b.template.clone(),
+                b.strict_template,
+            ),
+            None => return,
+        }
     };
+    let user = fetch_user_for(&template).await;
 
-    log::info!("📋 批量任务开始执行: batch_id={}, 任务数={}", batch_id, task_count);
+    // 等待排期的开始时间
+    if let Some(start_at) = schedule.as_ref().and_then(|s| s.start_at_utc()) {
+        if start_at > Utc::now() && !wait_until(&batch_id, start_at).await {
+            cancel_remaining(&batch_id, 0);
+            finalize_batch(&batch_id, true);
+            return;
+        }
+    }
+    {
+        let mut batches = BATCH_TASKS.lock();
+        if let Some(batch) = batches.get_mut(&batch_id) {
+            batch.state = BatchState::Running;
+        }
+    }
+
+    log::info!(
+        "{}",
+        i18n::tf(Msg::LogBatchExecute, &[&batch_id, &task_count])
+    );
+
+    let mut cancelled = false;
 
     for i in 0..task_count {
-        // 检查取消标记
+        // 跳过审核时被划掉的任务
         {
-            let flags = CANCEL_FLAGS.lock();
-            if flags.get(&batch_id).copied().unwrap_or(false) {
-                log::warn!("⏹️ 批量任务被取消: batch_id={}, 已完成={}/{}", batch_id, i, task_count);
-                // 将剩余任务标记为取消
-                let mut batches = BATCH_TASKS.lock();
-                if let Some(batch) = batches.get_mut(&batch_id) {
-                    for task in batch.tasks.iter_mut().skip(i) {
-                        if task.status == TaskStatus::Pending {
-                            task.status = TaskStatus::Cancelled;
-                        }
-                    }
+            let batches = BATCH_TASKS.lock();
+            match batches.get(&batch_id) {
+                Some(batch) if batch.tasks[i].status != TaskStatus::Pending => continue,
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        // 检查取消标记
+        if is_cancelled(&batch_id) {
+            log::warn!(
+                "{}",
+                i18n::tf(Msg::LogBatchCancelled, &[&batch_id, &i, &task_count])
+            );
+            cancel_remaining(&batch_id, i);
+            cancelled = true;
+            break;
+        }
+
+        // 不在允许的时间段内则等待
+        if let Some(schedule) = &schedule {
+            let now = Utc::now();
+            let next = schedule.next_allowed(now);
+            if next > now {
+                log::info!(
+                    "{}",
+                    i18n::tf(Msg::LogOutsideWindow, &[&next.to_rfc3339(), &batch_id])
+                );
+                if !wait_until(&batch_id, next).await {
+                    cancel_remaining(&batch_id, i);
+                    cancelled = true;
+                    break;
                 }
-                break;
             }
         }
 
-        // 获取当前任务信息
-        let (aid, content, title) = {
+        // 获取当前任务信息，尚未渲染的任务按视频渲染模板
+        let (video, rendered) = {
             let mut batches = BATCH_TASKS.lock();
             if let Some(batch) = batches.get_mut(&batch_id) {
-                batch.tasks[i].status = TaskStatus::Running;
-                let title = batch.tasks[i].video.title.clone();
-                (batch.tasks[i].video.aid, batch.tasks[i].content.clone(), title)
+                let task = &mut batch.tasks[i];
+                task.status = TaskStatus::Running;
+                let rendered = if task.rendered {
+                    Ok(())
+                } else {
+                    render_task(task, &template, user.as_ref(), strict)
+                };
+                (task.video.clone(), rendered.map(|_| task.content.clone()))
             } else {
                 break;
             }
         };
+        let aid = video.aid;
+        let title = &video.title;
 
         log::info!(
-            "▶️ 执行任务 [{}/{}]: aid={}, 标题=\"{}\"",
-            i + 1,
-            task_count,
-            aid,
-            truncate_str(&title, 20)
+            "{}",
+            i18n::tf(
+                Msg::LogTaskExecute,
+                &[&(i + 1), &task_count, &aid, &truncate_str(title, 20)]
+            )
         );
 
+        let content = match rendered {
+            Ok(content) => content,
+            Err(e) => {
+                let reason = e.to_user_message();
+                log::warn!("{}", i18n::tf(Msg::LogTaskSkipped, &[&aid, &reason]));
+                finish_task(&batch_id, i, TaskStatus::Skipped, Some(reason), None);
+                persist_progress(&batch_id, Some(i));
+                continue;
+            }
+        };
+
+        // 预检: 敏感词、屏蔽/允许名单、去重、每日配额
+        if let Err(reason) = precheck_task(&batch_id, i, &video, &content) {
+            log::warn!("{}", i18n::tf(Msg::LogTaskSkipped, &[&aid, &reason]));
+            finish_task(&batch_id, i, TaskStatus::Skipped, Some(reason), None);
+            persist_progress(&batch_id, Some(i));
+            continue;
+        }
+
+        // 评论区状态: 接口错误与发送评论一样按可重试和严重程度处理
+        let area = retry_request(
+            || comment::check_comment_area(aid),
+            |result: &Result<(), BiliApiError>| result.as_ref().is_err_and(|e| e.is_retryable()),
+            Msg::LogPrecheckRetry,
+            aid,
+        )
+        .await;
+        if let Err(e) = area {
+            let severity = e.severity();
+            let reason = e.to_user_message();
+            if matches!(e, BiliApiError::ApiError { .. }) && severity == ErrorSeverity::Error {
+                // 评论区关闭、视频不存在等: 该视频无法评论，跳过
+                log::warn!("{}", i18n::tf(Msg::LogTaskSkipped, &[&aid, &reason]));
+                finish_task(&batch_id, i, TaskStatus::Skipped, Some(reason), e.code());
+            } else {
+                finish_task(&batch_id, i, TaskStatus::Failed, Some(reason), e.code());
+            }
+            if severity == ErrorSeverity::Fatal {
+                abort_remaining(&batch_id, i, task_count);
+                break;
+            }
+            persist_progress(&batch_id, Some(i));
+            continue;
+        }
+
+        // 演练模式: 记录将要发送的内容，不调用发送接口
+        if dry_run {
+            log::info!(
+                "{}",
+                i18n::tf(Msg::LogDryRunTask, &[&aid, &truncate_str(&content, 30)])
+            );
+            finish_task(&batch_id, i, TaskStatus::WouldSend, None, None);
+            persist_progress(&batch_id, Some(i));
+            continue;
+        }
+
+        // 发送前保存执行中状态，发送期间退出时重启后不会重复发送
+        persist_progress(&batch_id, Some(i));
+
         // 执行评论 (带频率限制)
-        let result = comment::send_comment_with_rate_limit(aid, &content).await;
+        let result = retry_request(
+            || comment::send_comment_with_rate_limit(aid, &content),
+            is_retryable,
+            Msg::LogTaskRetry,
+            aid,
+        )
+        .await;
+        let severity = result_severity(&result);
 
         // 更新任务状态
-        {
-            let mut batches = BATCH_TASKS.lock();
-            if let Some(batch) = batches.get_mut(&batch_id) {
-                match result {
-                    Ok(r) if r.success => {
-                        batch.tasks[i].status = TaskStatus::Success;
-                        batch.success += 1;
-                    }
-                    Ok(r) => {
-                        batch.tasks[i].status = TaskStatus::Failed;
-                        batch.tasks[i].error_msg = r.error_msg;
-                        batch.failed += 1;
-                    }
-                    Err(e) => {
-                        batch.tasks[i].status = TaskStatus::Failed;
-                        batch.tasks[i].error_msg = Some(e.to_user_message());
-                        batch.failed += 1;
-                    }
-                }
-                batch.tasks[i].completed_at = Some(chrono::Utc::now().timestamp());
-                batch.completed += 1;
+        match result {
+            Ok(r) if r.success => {
+                finish_task(&batch_id, i, TaskStatus::Success, None, None);
+                record_sent(&batch_id, i, r.rpid);
+            }
+            Ok(r) => finish_task(&batch_id, i, TaskStatus::Failed, r.error_msg, r.error_code),
+            Err(e) => finish_task(
+                &batch_id,
+                i,
+                TaskStatus::Failed,
+                Some(e.to_user_message()),
+                e.code(),
+            ),
+        }
+
+        // 账号级错误，继续发送没有意义，中止剩余任务
+        if severity == Some(ErrorSeverity::Fatal) {
+            abort_remaining(&batch_id, i, task_count);
+            break;
+        }
+
+        persist_progress(&batch_id, Some(i));
+    }
+
+    finalize_batch(&batch_id, cancelled);
+}
+
+/// 执行请求，可重试的错误最多重试 task_max_retries 次
+async fn retry_request<T, Fut>(
+    request: impl Fn() -> Fut,
+    retryable: impl Fn(&T) -> bool,
+    retry_msg: Msg,
+    aid: u64,
+) -> T
+where
+    Fut: Future<Output = T>,
+{
+    let max_retries = settings::current().task_max_retries;
+    let mut retries = 0;
+    loop {
+        let result = request().await;
+        if retries >= max_retries || !retryable(&result) {
+            return result;
+        }
+        retries += 1;
+        log::warn!("{}", i18n::tf(retry_msg, &[&aid, &retries, &max_retries]));
+    }
+}
+
+/// 遇到账号级错误时中止批次: 第 index 个之后未执行的任务标记为取消
+fn abort_remaining(batch_id: &str, index: usize, task_count: usize) {
+    log::error!(
+        "{}",
+        i18n::tf(Msg::LogBatchAbort, &[&batch_id, &(index + 1), &task_count])
+    );
+    let mut batches = BATCH_TASKS.lock();
+    if let Some(batch) = batches.get_mut(batch_id) {
+        let now = chrono::Utc::now().timestamp();
+        let mut aborted = 0;
+        for task in batch.tasks.iter_mut().skip(index + 1) {
+            if task.status == TaskStatus::Pending {
+                task.status = TaskStatus::Cancelled;
+                task.error_msg = Some(i18n::t(Msg::BatchAbortedAccountError).to_string());
+                task.completed_at = Some(now);
+                aborted += 1;
             }
         }
+        // 计入已完成，便于前端判断任务结束
+        batch.completed += aborted;
     }
+}
 
+/// 将剩余未执行的任务标记为取消
+fn cancel_remaining(batch_id: &str, from: usize) {
+    let mut batches = BATCH_TASKS.lock();
+    if let Some(batch) = batches.get_mut(batch_id) {
+        for task in batch.tasks.iter_mut().skip(from) {
+            if task.status == TaskStatus::Pending {
+                task.status = TaskStatus::Cancelled;
+            }
+        }
+    }
+}
+
+/// 批次结束: 更新最终状态、保存并清理取消标记
+fn finalize_batch(batch_id: &str, cancelled: bool) {
     // 获取最终统计
     let (success, failed, total) = {
-        let batches = BATCH_TASKS.lock();
-        if let Some(batch) = batches.get(&batch_id) {
+        let mut batches = BATCH_TASKS.lock();
+        if let Some(batch) = batches.get_mut(batch_id) {
+            batch.state = if cancelled {
+                BatchState::Cancelled
+            } else {
+                BatchState::Finished
+            };
+            batch.next_run_at = None;
             (batch.success, batch.failed, batch.total)
         } else {
             (0, 0, 0)
         }
     };
+    persist_batch(batch_id);
 
     log::info!(
-        "🏁 批量任务完成: batch_id={}, 成功={}, 失败={}, 总计={}",
-        batch_id,
-        success,
-        failed,
-        total
+        "{}",
+        i18n::tf(
+            Msg::LogBatchFinished,
+            &[&batch_id, &success, &failed, &total]
+        )
     );
 
     // 清理取消标记
     {
         let mut flags = CANCEL_FLAGS.lock();
-        flags.remove(&batch_id);
+        flags.remove(batch_id);
+    }
+}
+
+/// 记录任务结束状态并更新批次统计
+fn finish_task(
+    batch_id: &str,
+    index: usize,
+    status: TaskStatus,
+    error_msg: Option<String>,
+    error_code: Option<i32>,
+) {
+    let mut batches = BATCH_TASKS.lock();
+    if let Some(batch) = batches.get_mut(batch_id) {
+        match status {
+            // 演练模式的 WouldSend 不计入成功数，避免与实际发送混淆
+            TaskStatus::Success => batch.success += 1,
+            TaskStatus::Failed => batch.failed += 1,
+            TaskStatus::Skipped => batch.skipped += 1,
+            _ => {}
+        }
+        let task = &mut batch.tasks[index];
+        task.status = status;
+        task.error_msg = error_msg;
+        task.error_code = error_code;
+        task.completed_at = Some(chrono::Utc::now().timestamp());
+        batch.completed += 1;
+    }
+}
+
+/// 记录发送成功的评论: 保存评论 ID (用于之后查询互动数据) 并写入已发送评论记录
+fn record_sent(batch_id: &str, index: usize, rpid: Option<u64>) {
+    let entry = {
+        let mut batches = BATCH_TASKS.lock();
+        let Some(batch) = batches.get_mut(batch_id) else {
+            return;
+        };
+        let template_id = batch.template_id.clone();
+        let task = &mut batch.tasks[index];
+        task.rpid = rpid;
+        CommentLedgerEntry {
+            aid: task.video.aid,
+            bvid: task.video.bvid.clone(),
+            rpid,
+            content: task.content.clone(),
+            batch_id: Some(batch_id.to_string()),
+            template_id,
+            sent_at: task
+                .completed_at
+                .unwrap_or_else(|| chrono::Utc::now().timestamp()),
+        }
+    };
+
+    if let Err(e) = ledger::record_comment(&entry) {
+        log::error!("写入已发送评论记录失败: {}", e);
+    }
+}
+
+/// 发送前的本地预检，返回跳过原因
+///
+/// 演练与正式执行使用相同的检查，演练结果即正式执行时会发送的评论
+fn precheck_task(
+    batch_id: &str,
+    index: usize,
+    video: &VideoItem,
+    content: &str,
+) -> Result<(), String> {
+    // 本地敏感词
+    let matched = moderation::check(content);
+    if !matched.is_empty() {
+        return Err(BiliApiError::SensitiveContent(matched.join(", ")).to_user_message());
+    }
+
+    // 屏蔽名单和仅允许名单模式
+    check_video_access(video)?;
+
+    // 去重: 已成功评论过的视频 (含已清除的批次)，或同一批次内重复的视频
+    if ledger::has_commented(video.aid)? {
+        return Err(i18n::t(Msg::SkipDuplicate).to_string());
+    }
+
+    let since = chrono::Utc::now().timestamp() - 24 * 3600;
+    let sent_elsewhere = ledger::count_since(since, batch_id)?;
+
+    let batches = BATCH_TASKS.lock();
+    let Some(batch) = batches.get(batch_id) else {
+        return Ok(());
+    };
+
+    let duplicated = batch.tasks[..index]
+        .iter()
+        .any(|t| t.video.aid == video.aid && t.status != TaskStatus::Skipped);
+    if duplicated {
+        return Err(i18n::t(Msg::SkipDuplicate).to_string());
+    }
+
+    // 配额: 最近 24 小时内其他来源已发送的评论数，加上本批次已发送 (演练时为将要发送) 的评论数
+    let quota = settings::current().daily_comment_quota;
+    let sent_by_batch = batch
+        .tasks
+        .iter()
+        .filter(|t| match t.status {
+            TaskStatus::WouldSend => true,
+            TaskStatus::Success => t.completed_at.is_some_and(|at| at >= since),
+            _ => false,
+        })
+        .count();
+    if sent_elsewhere + sent_by_batch >= quota {
+        return Err(i18n::tf(Msg::SkipQuotaExceeded, &[&quota]));
+    }
+
+    Ok(())
+}
+
+/// 判断评论结果是否可重试
+fn is_retryable(result: &Result<CommentResult, BiliApiError>) -> bool {
+    match result {
+        Ok(r) if r.success => false,
+        Ok(r) => r
+            .error_code
+            .and_then(error_code::lookup)
+            .is_some_and(|info| info.retryable),
+        Err(e) => e.is_retryable(),
+    }
+}
+
+/// 评论结果的错误严重程度 (成功时为 None)
+fn result_severity(result: &Result<CommentResult, BiliApiError>) -> Option<ErrorSeverity> {
+    match result {
+        Ok(r) if r.success => None,
+        Ok(r) => Some(
+            r.error_code
+                .and_then(error_code::lookup)
+                .map(|info| info.severity)
+                .unwrap_or(ErrorSeverity::Error),
+        ),
+        Err(e) => Some(e.severity()),
     }
 }
 
@@ -197,23 +848,42 @@ pub fn get_batch_status(batch_id: String) -> Result<BatchStatus, String> {
     batches
         .get(&batch_id)
         .cloned()
-        .ok_or_else(|| "批次不存在".to_string())
+        .ok_or_else(|| i18n::t(Msg::BatchNotFound).to_string())
 }
 
 /// 取消批量任务
 #[tauri::command]
 pub fn cancel_batch(batch_id: String) -> Result<(), String> {
-    log::info!("⏹️ Command: 取消批量任务 batch_id={}", batch_id);
+    log::info!("{}", i18n::tf(Msg::LogCancelBatch, &[&batch_id]));
     let mut flags = CANCEL_FLAGS.lock();
     flags.insert(batch_id, true);
     Ok(())
 }
 
-/// 清理已完成的批次
+/// 清理已完成的批次 (只从当前列表移除，批次历史仍保留)
 #[tauri::command]
 pub fn clear_batch(batch_id: String) {
-    let mut batches = BATCH_TASKS.lock();
-    batches.remove(&batch_id);
+    BATCH_TASKS.lock().remove(&batch_id);
+}
+
+/// 从批次历史中永久删除批次 (正在执行或已排期的批次需先取消)
+#[tauri::command]
+pub fn delete_batch(batch_id: String) -> Result<(), String> {
+    {
+        let mut batches = BATCH_TASKS.lock();
+        let active = batches
+            .get(&batch_id)
+            .is_some_and(|b| matches!(b.state, BatchState::Running | BatchState::Scheduled));
+        if active {
+            return Err(i18n::t(Msg::BatchActive).to_string());
+        }
+        batches.remove(&batch_id);
+    }
+
+    let _guard = storage::lock();
+    batch_storage::delete_batch(&batch_id)?;
+    log::info!("批次已删除: batch_id={}", batch_id);
+    Ok(())
 }
 
 /// 获取评论间隔时间
@@ -221,3 +891,9 @@ pub fn clear_batch(batch_id: String) {
 pub fn get_comment_interval() -> u64 {
     comment::get_comment_interval()
 }
+
+/// 获取已知错误码表
+#[tauri::command]
+pub fn get_error_codes() -> Vec<ErrorCodeInfo> {
+    error_code::ERROR_CODES.to_vec()
+}
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use crate::api::BILI_CLIENT;
+use crate::commands::comment;
+use crate::i18n::{self, Msg};
+use crate::models::{BackupSummary, DataLocation, StorageIssue};
+use crate::storage::{self, backup};
+
+/// 获取本次运行中遇到的存储文件问题 (损坏、已隔离、是否已从备份恢复)
+#[tauri::command]
+pub fn get_storage_issues() -> Vec<StorageIssue> {
+    storage::storage_issues()
+}
+
+/// 清除已提示过的存储文件问题
+#[tauri::command]
+pub fn clear_storage_issues() {
+    storage::clear_storage_issues();
+}
+
+/// 获取当前使用的数据目录及其来源 (命令行、环境变量、便携模式或默认)
+#[tauri::command]
+pub fn get_data_location() -> DataLocation {
+    storage::data_location()
+}
+
+/// 导出模板、设置、批次历史和评论记录到一个备份文件
+///
+/// `include_credential` 为 true 时同时导出登录凭证，必须提供密码用于加密
+#[tauri::command]
+pub fn export_backup(
+    path: PathBuf,
+    include_credential: Option<bool>,
+    password: Option<String>,
+) -> Result<BackupSummary, String> {
+    let password = if include_credential.unwrap_or(false) {
+        match password.filter(|p| !p.is_empty()) {
+            Some(p) => Some(p),
+            None => return Err(i18n::t(Msg::BackupPasswordRequired).to_string()),
+        }
+    } else {
+        None
+    };
+
+    backup::export_backup(&path, password.as_deref())
+}
+
+/// 从备份文件恢复数据 (替换当前的模板、设置、批次历史和评论记录)
+///
+/// 备份中的登录凭证仅在提供密码时恢复；导入前的数据会自动备份到数据目录的 `backups` 下
+#[tauri::command]
+pub fn import_backup(path: PathBuf, password: Option<String>) -> Result<BackupSummary, String> {
+    // 恢复和重新加载批次期间不允许其他命令写入，避免旧批次被重新保存；
+    // 先加锁再检查，避免检查之后启动的批次在重新加载时被清除
+    let _guard = storage::lock();
+    if comment::has_active_batches() {
+        return Err(i18n::t(Msg::BackupBatchActive).to_string());
+    }
+
+    let password = password.filter(|p| !p.is_empty());
+    let (summary, credential) = backup::import_backup(&path, password.as_deref())?;
+
+    comment::reload_batches();
+    i18n::set_locale(storage::settings::current().locale);
+    if credential.is_some() {
+        BILI_CLIENT.set_credential(credential);
+    }
+
+    Ok(summary)
+}
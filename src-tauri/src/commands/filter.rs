@@ -0,0 +1,114 @@
+use uuid::Uuid;
+
+use crate::i18n::{self, Msg};
+use crate::models::{FilterOutcome, FilterPreset, VideoFilterRules, VideoItem};
+use crate::storage::{self, filter_preset};
+use crate::video_filter::VideoFilter;
+
+/// 加载预设并编译筛选规则，未指定预设时返回 None
+pub(super) fn load_filter(preset_id: Option<&str>) -> Result<Option<VideoFilter>, String> {
+    let Some(id) = preset_id else {
+        return Ok(None);
+    };
+    let preset = filter_preset::get_preset(id)?
+        .ok_or_else(|| i18n::t(Msg::FilterPresetNotFound).to_string())?;
+    VideoFilter::compile(&preset.rules).map(Some)
+}
+
+/// 按预设筛选视频 (未指定预设时原样返回)，排除的视频记录日志
+pub(super) fn filter_videos(
+    videos: Vec<VideoItem>,
+    preset_id: Option<&str>,
+) -> Result<Vec<VideoItem>, String> {
+    let Some(filter) = load_filter(preset_id)? else {
+        return Ok(videos);
+    };
+
+    let outcome = filter.apply(videos);
+    for rejected in &outcome.rejected {
+        log::info!(
+            "视频已被筛选规则排除: aid={}, {}",
+            rejected.video.aid,
+            rejected.reason
+        );
+    }
+    Ok(outcome.kept)
+}
+
+/// 获取所有筛选预设
+#[tauri::command]
+pub fn get_filter_presets() -> Result<Vec<FilterPreset>, String> {
+    filter_preset::load_presets()
+}
+
+/// 保存筛选预设 (`id` 为空时新建)，返回保存后的预设
+#[tauri::command]
+pub fn save_filter_preset(
+    id: Option<String>,
+    name: String,
+    rules: VideoFilterRules,
+) -> Result<FilterPreset, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(i18n::t(Msg::FilterPresetNameRequired).to_string());
+    }
+    VideoFilter::compile(&rules)?;
+
+    let _guard = storage::lock();
+    let now = chrono::Utc::now().timestamp();
+    let preset = match id {
+        Some(id) => {
+            let existing = filter_preset::get_preset(&id)?
+                .ok_or_else(|| i18n::t(Msg::FilterPresetNotFound).to_string())?;
+            FilterPreset {
+                name,
+                rules,
+                updated_at: now,
+                ..existing
+            }
+        }
+        None => FilterPreset {
+            id: Uuid::new_v4().to_string(),
+            name,
+            rules,
+            created_at: now,
+            updated_at: now,
+        },
+    };
+
+    filter_preset::save_preset(&preset)?;
+    Ok(preset)
+}
+
+/// 删除筛选预设
+#[tauri::command]
+pub fn delete_filter_preset(id: String) -> Result<(), String> {
+    if filter_preset::delete_preset(&id)? {
+        Ok(())
+    } else {
+        Err(i18n::t(Msg::FilterPresetNotFound).to_string())
+    }
+}
+
+/// 按筛选规则 (或已保存的预设) 筛选视频，返回保留和排除的视频及原因
+#[tauri::command]
+pub fn apply_video_filter(
+    videos: Vec<VideoItem>,
+    rules: Option<VideoFilterRules>,
+    preset_id: Option<String>,
+) -> Result<FilterOutcome, String> {
+    let filter = match rules {
+        Some(rules) => VideoFilter::compile(&rules)?,
+        None => match load_filter(preset_id.as_deref())? {
+            Some(filter) => filter,
+            None => {
+                return Ok(FilterOutcome {
+                    kept: videos,
+                    rejected: Vec::new(),
+                })
+            }
+        },
+    };
+
+    Ok(filter.apply(videos))
+}
@@ -3,4 +3,11 @@
 pub mod auth;
 pub mod search;
 pub mod comment;
+pub mod sensitive;
+pub mod review;
 pub mod template;
+pub mod settings;
+pub mod data;
+pub mod filter;
+pub mod access_list;
+pub mod video;
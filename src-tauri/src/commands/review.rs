@@ -0,0 +1,227 @@
+use uuid::Uuid;
+
+use crate::i18n::{self, Msg};
+use crate::models::{BatchSchedule, BatchState, BatchStatus, TaskStatus, VideoItem};
+use crate::storage;
+
+use super::comment::{
+    attach_template, batch_history, fetch_user_for, mark_started, new_batch, persist_batch,
+    render_task, spawn_batch, BATCH_TASKS,
+};
+use super::filter::filter_videos;
+
+/// 校验并规范化人员标识
+fn normalize_person(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(i18n::t(Msg::ReviewerRequired).to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// 审核人不能与创建人相同
+fn ensure_second_person(batch: &BatchStatus, approver: &str) -> Result<(), String> {
+    let same = batch
+        .created_by
+        .as_deref()
+        .is_some_and(|creator| creator.eq_ignore_ascii_case(approver));
+    if same {
+        return Err(i18n::t(Msg::ApproverIsCreator).to_string());
+    }
+    Ok(())
+}
+
+/// 对草稿批次执行修改，并保存结果
+fn modify_draft<F>(batch_id: &str, approver: &str, f: F) -> Result<BatchStatus, String>
+where
+    F: FnOnce(&mut BatchStatus) -> Result<(), String>,
+{
+    let updated = {
+        let mut batches = BATCH_TASKS.lock();
+        let batch = batches
+            .get_mut(batch_id)
+            .ok_or_else(|| i18n::t(Msg::BatchNotFound).to_string())?;
+
+        if batch.state != BatchState::Draft {
+            return Err(i18n::t(Msg::BatchNotDraft).to_string());
+        }
+        ensure_second_person(batch, approver)?;
+
+        f(batch)?;
+        batch.clone()
+    };
+
+    persist_batch(batch_id);
+    Ok(updated)
+}
+
+/// 创建待审核的批次草稿，评论内容在创建时按视频渲染以便审核
+///
+/// `filter_preset_id` 指定时先按筛选预设排除不符合规则的视频
+#[tauri::command]
+pub async fn create_draft_batch(
+    videos: Vec<VideoItem>,
+    content: String,
+    created_by: String,
+    strict_template: Option<bool>,
+    template_id: Option<String>,
+    filter_preset_id: Option<String>,
+) -> Result<BatchStatus, String> {
+    let created_by = normalize_person(&created_by)?;
+    let strict = strict_template.unwrap_or(false);
+    let videos = filter_videos(videos, filter_preset_id.as_deref())?;
+
+    let mut batch = new_batch(
+        &videos,
+        &content,
+        BatchState::Draft,
+        Some(created_by.clone()),
+    );
+    batch.strict_template = strict;
+    attach_template(&mut batch, template_id);
+
+    let user = fetch_user_for(&content).await;
+    for task in batch.tasks.iter_mut() {
+        render_task(task, &content, user.as_ref(), strict).map_err(|e| e.to_user_message())?;
+    }
+
+    log::info!(
+        "{}",
+        i18n::tf(Msg::LogDraftCreated, &[&batch.batch_id, &batch.total, &created_by])
+    );
+
+    BATCH_TASKS
+        .lock()
+        .insert(batch.batch_id.clone(), batch.clone());
+    persist_batch(&batch.batch_id);
+
+    Ok(batch)
+}
+
+/// 获取批次列表 (可按状态筛选，最新的在前)
+#[tauri::command]
+pub fn list_batches(state: Option<BatchState>) -> Result<Vec<BatchStatus>, String> {
+    let mut batches: Vec<BatchStatus> = batch_history()?
+        .into_iter()
+        .filter(|b| state.map_or(true, |s| b.state == s))
+        .collect();
+    batches.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    Ok(batches)
+}
+
+/// 审核单个任务: 保留或划掉
+#[tauri::command]
+pub fn review_task(
+    batch_id: String,
+    task_id: String,
+    approver: String,
+    keep: bool,
+    note: Option<String>,
+) -> Result<BatchStatus, String> {
+    let approver = normalize_person(&approver)?;
+
+    modify_draft(&batch_id, &approver, |batch| {
+        let task = batch
+            .tasks
+            .iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| i18n::t(Msg::TaskNotFound).to_string())?;
+
+        task.status = if keep {
+            TaskStatus::Pending
+        } else {
+            TaskStatus::Struck
+        };
+        task.review_note = note;
+        Ok(())
+    })
+}
+
+/// 审核通过批次，只有保留的任务会被执行
+#[tauri::command]
+pub fn approve_batch(batch_id: String, approver: String) -> Result<BatchStatus, String> {
+    let approver = normalize_person(&approver)?;
+
+    let batch = modify_draft(&batch_id, &approver, |batch| {
+        let approved = batch
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Pending)
+            .count();
+        if approved == 0 {
+            return Err(i18n::t(Msg::NoApprovedTasks).to_string());
+        }
+
+        batch.total = approved;
+        batch.state = BatchState::Approved;
+        batch.approved_by = Some(approver.clone());
+        batch.approved_at = Some(chrono::Utc::now().timestamp());
+        Ok(())
+    })?;
+
+    log::info!(
+        "{}",
+        i18n::tf(Msg::LogBatchApproved, &[&batch_id, &batch.total, &approver])
+    );
+
+    Ok(batch)
+}
+
+/// 复制已审核的批次用于演练: 使用新的批次和任务 ID，不带排期
+fn dry_run_copy(batch: &BatchStatus) -> BatchStatus {
+    let now = chrono::Utc::now().timestamp();
+    let mut copy = batch.clone();
+    copy.batch_id = Uuid::new_v4().to_string();
+    copy.created_at = now;
+    copy.schedule = None;
+    copy.next_run_at = None;
+    for task in copy.tasks.iter_mut() {
+        task.id = Uuid::new_v4().to_string();
+        task.created_at = now;
+    }
+    copy
+}
+
+/// 执行已审核通过的批次，返回执行的批次 ID
+///
+/// `dry_run` 为 true 时在批次的副本上演练并返回副本的 ID，
+/// 原批次保持已审核状态，之后仍可正式执行
+#[tauri::command]
+pub async fn execute_batch(
+    batch_id: String,
+    dry_run: Option<bool>,
+    schedule: Option<BatchSchedule>,
+) -> Result<String, String> {
+    if let Some(schedule) = &schedule {
+        schedule.validate()?;
+    }
+
+    // 状态检查与切换在同一次加锁中完成，重复调用时只有一次能通过；
+    // 同时持有存储锁，与恢复备份互斥
+    let _guard = storage::lock();
+    let run_id = {
+        let mut batches = BATCH_TASKS.lock();
+        let batch = batches
+            .get_mut(&batch_id)
+            .ok_or_else(|| i18n::t(Msg::BatchNotFound).to_string())?;
+        if batch.state != BatchState::Approved {
+            return Err(i18n::t(Msg::BatchNotApproved).to_string());
+        }
+
+        if dry_run.unwrap_or(false) {
+            let mut copy = dry_run_copy(batch);
+            mark_started(&mut copy, true);
+            let run_id = copy.batch_id.clone();
+            batches.insert(run_id.clone(), copy);
+            run_id
+        } else {
+            batch.schedule = schedule;
+            mark_started(batch, false);
+            batch_id.clone()
+        }
+    };
+
+    log::info!("{}", i18n::tf(Msg::LogExecuteApproved, &[&run_id]));
+    spawn_batch(&run_id);
+    Ok(run_id)
+}
@@ -1,15 +1,217 @@
-use crate::api::search;
-use crate::models::SearchResult;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::time::sleep;
+use uuid::Uuid;
 
-/// 搜索视频
+use crate::api::search::{self, MAX_PAGE_SIZE, MAX_SEARCH_PAGES};
+use crate::video_filter::VideoFilter;
+use crate::i18n::{self, Msg};
+use crate::models::{SearchCollectEvent, SearchFilters, SearchOrder, SearchResult, VideoItem};
+use crate::storage::settings;
+
+use super::filter::{filter_videos, load_filter};
+
+/// 多页搜索进度事件名
+const SEARCH_COLLECT_EVENT: &str = "search-collect";
+
+/// 单次多页搜索最多收集的视频数 (搜索接口的页数上限 × 单页上限)
+const MAX_COLLECT_ITEMS: usize = (MAX_SEARCH_PAGES * MAX_PAGE_SIZE) as usize;
+
+/// 多页搜索的取消标记
+static COLLECT_CANCEL_FLAGS: Lazy<Mutex<HashMap<String, bool>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 搜索视频 (未指定排序时使用综合排序，无效的排序方式在参数解析时报错)
+///
+/// 指定 `filter_preset_id` 时按筛选预设排除本页中不符合规则的视频
 #[tauri::command]
 pub async fn search_videos(
     keyword: String,
     page: u32,
     page_size: u32,
-    order: Option<String>,
+    order: Option<SearchOrder>,
+    filters: Option<SearchFilters>,
+    filter_preset_id: Option<String>,
 ) -> Result<SearchResult, String> {
-    search::search_videos(&keyword, page, page_size, order.as_deref())
-        .await
-        .map_err(|e| e.to_user_message())
+    let filters = filters.unwrap_or_default();
+    filters.validate()?;
+
+    let mut result = search::search_videos(
+        &keyword,
+        page,
+        page_size,
+        order.unwrap_or_default(),
+        &filters,
+    )
+    .await
+    .map_err(|e| e.to_user_message())?;
+
+    result.items = filter_videos(result.items, filter_preset_id.as_deref())?;
+    Ok(result)
+}
+
+/// 多页搜索并按 aid 去重，最多收集 `max_items` 个视频
+///
+/// 立即返回收集 ID，结果通过 `search-collect` 事件逐页推送，可用 `cancel_collect` 中途取消；
+/// 指定 `filter_preset_id` 时只收集符合筛选预设的视频
+#[tauri::command]
+pub fn collect_search(
+    app: AppHandle,
+    keyword: String,
+    filters: Option<SearchFilters>,
+    max_items: usize,
+    order: Option<SearchOrder>,
+    filter_preset_id: Option<String>,
+) -> Result<String, String> {
+    let keyword = keyword.trim().to_string();
+    if keyword.is_empty() {
+        return Err(i18n::t(Msg::KeywordRequired).to_string());
+    }
+    if max_items == 0 || max_items > MAX_COLLECT_ITEMS {
+        return Err(i18n::tf(Msg::CollectMaxItemsRange, &[&MAX_COLLECT_ITEMS]));
+    }
+    let filters = filters.unwrap_or_default();
+    filters.validate()?;
+    let video_filter = load_filter(filter_preset_id.as_deref())?;
+
+    let collect_id = Uuid::new_v4().to_string();
+    COLLECT_CANCEL_FLAGS
+        .lock()
+        .insert(collect_id.clone(), false);
+
+    let id = collect_id.clone();
+    let order = order.unwrap_or_default();
+    tauri::async_runtime::spawn(async move {
+        run_collect(app, id, keyword, filters, order, max_items, video_filter).await;
+    });
+
+    Ok(collect_id)
+}
+
+/// 取消多页搜索 (当前页完成后停止)
+#[tauri::command]
+pub fn cancel_collect(collect_id: String) {
+    log::info!("取消多页搜索: collect_id={}", collect_id);
+    if let Some(flag) = COLLECT_CANCEL_FLAGS.lock().get_mut(&collect_id) {
+        *flag = true;
+    }
+}
+
+/// 多页搜索是否已被取消
+fn is_collect_cancelled(collect_id: &str) -> bool {
+    COLLECT_CANCEL_FLAGS
+        .lock()
+        .get(collect_id)
+        .copied()
+        .unwrap_or(false)
+}
+
+/// 推送多页搜索进度
+fn emit_progress(app: &AppHandle, event: SearchCollectEvent) {
+    if let Err(e) = app.emit(SEARCH_COLLECT_EVENT, event) {
+        log::warn!("推送搜索进度失败: {}", e);
+    }
+}
+
+/// 逐页搜索直到收集足够的视频、没有更多结果、达到页数上限或被取消
+async fn run_collect(
+    app: AppHandle,
+    collect_id: String,
+    keyword: String,
+    filters: SearchFilters,
+    order: SearchOrder,
+    max_items: usize,
+    video_filter: Option<VideoFilter>,
+) {
+    log::info!(
+        "🔍 开始多页搜索: collect_id={}, keyword={}, max_items={}",
+        collect_id,
+        keyword,
+        max_items
+    );
+
+    let mut seen = HashSet::new();
+    let mut collected = 0;
+    let mut page = 0;
+    let mut cancelled = false;
+    let mut error = None;
+
+    while page < MAX_SEARCH_PAGES && collected < max_items {
+        if page > 0 {
+            // 每页之间按设置的间隔等待，避免触发风控
+            sleep(Duration::from_millis(
+                settings::current().collect_page_delay_ms,
+            ))
+            .await;
+        }
+        if is_collect_cancelled(&collect_id) {
+            cancelled = true;
+            break;
+        }
+        page += 1;
+
+        let result =
+            match search::search_videos(&keyword, page, MAX_PAGE_SIZE, order, &filters).await {
+                Ok(result) => result,
+                Err(e) => {
+                    error = Some(e.to_user_message());
+                    break;
+                }
+            };
+
+        let fetched = result.items.len();
+        let now = chrono::Utc::now().timestamp();
+        let items: Vec<VideoItem> = result
+            .items
+            .into_iter()
+            .filter(|v| seen.insert(v.aid))
+            .filter(|v| video_filter.as_ref().map_or(true, |f| f.check(v, now).is_ok()))
+            .take(max_items - collected)
+            .collect();
+        collected += items.len();
+
+        emit_progress(
+            &app,
+            SearchCollectEvent {
+                collect_id: collect_id.clone(),
+                page,
+                items,
+                collected,
+                finished: false,
+                cancelled: false,
+                error: None,
+            },
+        );
+
+        // 本页不满或已到最后一页，说明没有更多结果
+        let total_pages = (result.total as usize).div_ceil(MAX_PAGE_SIZE as usize);
+        if fetched < MAX_PAGE_SIZE as usize || page as usize >= total_pages {
+            break;
+        }
+    }
+
+    COLLECT_CANCEL_FLAGS.lock().remove(&collect_id);
+    log::info!(
+        "✅ 多页搜索结束: collect_id={}, 共 {} 页, 收集 {} 个视频{}",
+        collect_id,
+        page,
+        collected,
+        if cancelled { " (已取消)" } else { "" }
+    );
+
+    emit_progress(
+        &app,
+        SearchCollectEvent {
+            collect_id,
+            page,
+            items: Vec::new(),
+            collected,
+            finished: true,
+            cancelled,
+            error,
+        },
+    );
 }
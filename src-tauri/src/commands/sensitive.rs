@@ -0,0 +1,74 @@
+use std::fs;
+
+use crate::models::SensitiveWord;
+use crate::moderation::{self, WordMatcher};
+use crate::storage::{self, sensitive_words};
+
+/// 获取本地敏感词列表
+#[tauri::command]
+pub fn get_sensitive_words() -> Result<Vec<SensitiveWord>, String> {
+    sensitive_words::load_sensitive_words()
+}
+
+/// 保存本地敏感词列表 (正则表达式无效时拒绝保存)
+#[tauri::command]
+pub fn set_sensitive_words(words: Vec<SensitiveWord>) -> Result<(), String> {
+    // 先编译校验，保存成功后再替换生效的匹配器，避免内存与存储不一致
+    let matcher = WordMatcher::compile(&words)?;
+    let _guard = storage::lock();
+    sensitive_words::save_sensitive_words(&words)?;
+    moderation::set_matcher(matcher);
+    log::info!("敏感词列表已更新: {} 条", words.len());
+    Ok(())
+}
+
+/// 从文本文件导入敏感词 (每行一个，`re:` 开头为正则)，返回新增条数
+#[tauri::command]
+pub fn import_sensitive_words(path: String, replace: Option<bool>) -> Result<usize, String> {
+    let text = fs::read_to_string(&path).map_err(|e| format!("读取文件失败: {}", e))?;
+    let imported = sensitive_words::parse_word_list(&text);
+
+    let _guard = storage::lock();
+    let mut words = if replace.unwrap_or(false) {
+        Vec::new()
+    } else {
+        sensitive_words::load_sensitive_words()?
+    };
+
+    let before = words.len();
+    for word in imported {
+        let exists = words
+            .iter()
+            .any(|w| w.kind == word.kind && w.pattern == word.pattern);
+        if !exists {
+            words.push(word);
+        }
+    }
+    let added = words.len() - before;
+
+    set_sensitive_words(words)?;
+    log::info!("从 {} 导入敏感词 {} 条", path, added);
+    Ok(added)
+}
+
+/// 检查内容命中的本地敏感词
+#[tauri::command]
+pub fn check_sensitive_words(content: String) -> Vec<String> {
+    moderation::check(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WordKind;
+
+    #[test]
+    fn invalid_regex_is_rejected_before_saving() {
+        // 编译失败时直接返回，不会写入存储
+        let words = vec![SensitiveWord {
+            pattern: "[unclosed".to_string(),
+            kind: WordKind::Regex,
+        }];
+        assert!(set_sensitive_words(words).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::i18n::{self, Locale};
+use crate::models::Settings;
+use crate::storage::{self, settings};
+
+/// 获取当前语言
+#[tauri::command]
+pub fn get_locale() -> Locale {
+    i18n::current_locale()
+}
+
+/// 切换语言并保存到设置
+#[tauri::command]
+pub fn set_locale(locale: Locale) -> Result<(), String> {
+    let _guard = storage::lock();
+    let mut current = settings::current();
+    current.locale = locale;
+    settings::save_settings(&current)?;
+
+    i18n::set_locale(locale);
+    log::info!("界面语言已切换为 {:?}", locale);
+    Ok(())
+}
+
+/// 获取应用设置
+#[tauri::command]
+pub fn get_settings() -> Settings {
+    settings::current()
+}
+
+/// 校验并保存应用设置，返回保存后的设置
+///
+/// 评论间隔、重试、超时、User-Agent 等设置在下次请求时生效
+#[tauri::command]
+pub fn update_settings(settings: Settings) -> Result<Settings, String> {
+    settings.validate()?;
+
+    let _guard = storage::lock();
+    let mut updated = settings;
+    updated.user_agent = updated.user_agent.trim().to_string();
+    storage::settings::save_settings(&updated)?;
+
+    i18n::set_locale(updated.locale);
+    log::info!("设置已更新: {:?}", updated);
+    Ok(updated)
+}
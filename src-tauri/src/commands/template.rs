@@ -1,53 +1,488 @@
+use std::collections::{BTreeMap, HashMap};
+
 use uuid::Uuid;
 
-use crate::models::CommentTemplate;
-use crate::storage::template;
+use super::comment::batch_history;
+use crate::api::{comment, error_code, login};
+use crate::i18n::{self, Msg};
+use crate::models::{
+    BatchState, BatchStatus, CommentTemplate, FailureCodeCount, ImportConflict, ImportSummary,
+    TaskStatus, TemplateCategories, TemplateEngagement, TemplateFilter, TemplateFormat,
+    TemplateRevision, TemplateStats, VideoItem,
+};
+use crate::render::{self, RenderContext, TemplatePreview};
+use crate::storage::{self, template, template_exchange, template_revision};
+
+/// 每个模板最多查询互动数据的评论数 (取最近发送的)
+const ENGAGEMENT_SAMPLE_SIZE: usize = 20;
+
+/// 用示例视频校验模板内容，存在问题时返回错误
+fn validate_content(content: &str) -> Result<(), String> {
+    let video = render::sample_video();
+    let user = render::sample_user();
+    let ctx = RenderContext {
+        video: &video,
+        user: Some(&user),
+    };
+
+    let preview = render::preview(content, &ctx);
+    if preview.is_valid() {
+        Ok(())
+    } else {
+        Err(preview.issues_message())
+    }
+}
 
-/// 获取所有模板
+/// 规范化分类: 去除首尾空白，空字符串视为未分类
+fn normalize_category(category: Option<String>) -> Option<String> {
+    category
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+}
+
+/// 规范化标签: 去除首尾空白、空标签和重复标签
+fn normalize_tags(tags: Option<Vec<String>>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags.unwrap_or_default() {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !result.contains(&tag) {
+            result.push(tag);
+        }
+    }
+    result
+}
+
+/// 获取模板 (可按分类、标签、关键词筛选并排序)
 #[tauri::command]
-pub fn get_templates() -> Vec<CommentTemplate> {
-    template::load_templates()
+pub fn get_templates(filter: Option<TemplateFilter>) -> Result<Vec<CommentTemplate>, String> {
+    let templates = template::load_templates()?;
+    Ok(match filter {
+        Some(filter) => template::filter_templates(templates, &filter),
+        None => templates,
+    })
 }
 
 /// 创建模板
 #[tauri::command]
-pub fn create_template(name: String, content: String) -> Result<CommentTemplate, String> {
+pub fn create_template(
+    name: String,
+    content: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<CommentTemplate, String> {
+    validate_content(&content)?;
+
     let now = chrono::Utc::now().timestamp();
     let template = CommentTemplate {
         id: Uuid::new_v4().to_string(),
         name,
         content,
+        category: normalize_category(category),
+        tags: normalize_tags(tags),
         created_at: now,
         updated_at: now,
+        last_used_at: None,
+        revision: 1,
     };
 
     template::add_template(template.clone())?;
+    template_revision::record_revision(&template)?;
     Ok(template)
 }
 
-/// 更新模板
+/// 更新模板 (未传入 `category`/`tags` 时保留原值)
 #[tauri::command]
-pub fn update_template(id: String, name: String, content: String) -> Result<CommentTemplate, String> {
-    let templates = template::load_templates();
+pub fn update_template(
+    id: String,
+    name: String,
+    content: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<CommentTemplate, String> {
+    validate_content(&content)?;
+
+    let _guard = storage::lock();
+    let templates = template::load_templates()?;
     let existing = templates
         .iter()
         .find(|t| t.id == id)
-        .ok_or_else(|| "模板不存在".to_string())?;
+        .ok_or_else(|| i18n::t(Msg::TemplateNotFound).to_string())?;
+
+    // 旧版本数据没有修订记录，先补记更新前的内容
+    template_revision::record_revision(existing)?;
+
+    // 未传入的分类和标签保持不变，传入空值可清除
+    let category = match category {
+        Some(category) => normalize_category(Some(category)),
+        None => existing.category.clone(),
+    };
+    let tags = match tags {
+        Some(tags) => normalize_tags(Some(tags)),
+        None => existing.tags.clone(),
+    };
 
     let updated = CommentTemplate {
         id,
         name,
         content,
+        category,
+        tags,
         created_at: existing.created_at,
         updated_at: chrono::Utc::now().timestamp(),
+        last_used_at: existing.last_used_at,
+        revision: existing.revision + 1,
+    };
+
+    template::update_template(updated.clone())?;
+    template_revision::record_revision(&updated)?;
+    Ok(updated)
+}
+
+/// 获取模板的修订历史 (最新的在前)
+#[tauri::command]
+pub fn get_template_revisions(template_id: String) -> Result<Vec<TemplateRevision>, String> {
+    template_revision::get_revisions(&template_id)
+}
+
+/// 回滚模板到指定修订版本 (以旧内容创建一个新的修订版本)
+#[tauri::command]
+pub fn rollback_template(template_id: String, revision: u32) -> Result<CommentTemplate, String> {
+    let target = template_revision::get_revision(&template_id, revision)?
+        .ok_or_else(|| i18n::t(Msg::TemplateRevisionNotFound).to_string())?;
+    validate_content(&target.content)?;
+
+    let _guard = storage::lock();
+    let templates = template::load_templates()?;
+    let existing = templates
+        .iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| i18n::t(Msg::TemplateNotFound).to_string())?;
+
+    template_revision::record_revision(existing)?;
+
+    let updated = CommentTemplate {
+        name: target.name,
+        content: target.content,
+        updated_at: chrono::Utc::now().timestamp(),
+        revision: existing.revision + 1,
+        ..existing.clone()
     };
 
     template::update_template(updated.clone())?;
+    template_revision::record_revision(&updated)?;
+
+    log::info!(
+        "模板已回滚: id={}, 回滚到版本={}, 新版本={}",
+        template_id,
+        revision,
+        updated.revision
+    );
     Ok(updated)
 }
 
-/// 删除模板
+/// 删除模板 (修订历史保留用于审计)
 #[tauri::command]
 pub fn delete_template(id: String) -> Result<(), String> {
     template::delete_template(&id)
 }
+
+/// 预览模板: 用示例视频 (或指定视频) 渲染并报告问题
+#[tauri::command]
+pub async fn preview_template(
+    template_id: Option<String>,
+    content: Option<String>,
+    video: Option<VideoItem>,
+) -> Result<TemplatePreview, String> {
+    let content = match (content, template_id) {
+        (Some(content), _) => content,
+        (None, Some(id)) => template::load_templates()?
+            .into_iter()
+            .find(|t| t.id == id)
+            .map(|t| t.content)
+            .ok_or_else(|| i18n::t(Msg::TemplateNotFound).to_string())?,
+        (None, None) => return Err(i18n::t(Msg::TemplateContentRequired).to_string()),
+    };
+
+    let video = video.unwrap_or_else(render::sample_video);
+
+    // 已登录时使用真实用户信息，否则用户相关占位符会被报告为问题
+    let user = if render::needs_user(&content) {
+        login::get_user_info().await.ok().flatten()
+    } else {
+        None
+    };
+
+    let ctx = RenderContext {
+        video: &video,
+        user: user.as_ref(),
+    };
+
+    Ok(render::preview(&content, &ctx))
+}
+
+/// 获取所有已使用的分类和标签
+#[tauri::command]
+pub fn get_template_categories() -> Result<TemplateCategories, String> {
+    let templates = template::load_templates()?;
+
+    let mut categories: Vec<String> = templates
+        .iter()
+        .filter_map(|t| t.category.clone())
+        .collect();
+    categories.sort();
+    categories.dedup();
+
+    let mut tags: Vec<String> = templates.iter().flat_map(|t| t.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+
+    Ok(TemplateCategories { categories, tags })
+}
+
+/// 导出模板 (ids 为空时导出全部)
+#[tauri::command]
+pub fn export_templates(
+    format: TemplateFormat,
+    ids: Option<Vec<String>>,
+) -> Result<String, String> {
+    let templates: Vec<CommentTemplate> = template::load_templates()?
+        .into_iter()
+        .filter(|t| ids.as_ref().map_or(true, |ids| ids.contains(&t.id)))
+        .collect();
+
+    template_exchange::export_templates(&templates, format)
+}
+
+/// 导入模板
+///
+/// 与已有模板 ID 或名称相同时按 conflict 处理，内容校验未通过的模板不会导入
+#[tauri::command]
+pub fn import_templates(
+    data: String,
+    format: TemplateFormat,
+    conflict: Option<ImportConflict>,
+) -> Result<ImportSummary, String> {
+    let parsed = template_exchange::parse_templates(&data, format)?;
+    let conflict = conflict.unwrap_or_default();
+
+    let _guard = storage::lock();
+    let mut templates = template::load_templates()?;
+    let mut summary = ImportSummary {
+        errors: parsed.errors,
+        ..Default::default()
+    };
+    let now = chrono::Utc::now().timestamp();
+
+    for item in parsed.templates {
+        let name = item.name.trim().to_string();
+        if name.is_empty() {
+            summary.errors.push(format!(
+                "{}: {}",
+                i18n::t(Msg::TemplateUnnamed),
+                i18n::t(Msg::TemplateNameRequired)
+            ));
+            continue;
+        }
+        if let Err(e) = validate_content(&item.content) {
+            summary.errors.push(format!("{}: {}", name, e));
+            continue;
+        }
+
+        let existing = templates
+            .iter()
+            .position(|t| item.id.as_deref() == Some(t.id.as_str()) || t.name == name);
+
+        match (existing, conflict) {
+            (Some(_), ImportConflict::Skip) => summary.skipped += 1,
+            (Some(pos), ImportConflict::Overwrite) => {
+                template_revision::record_revision(&templates[pos])?;
+
+                let existing = &mut templates[pos];
+                existing.name = name;
+                existing.content = item.content;
+                existing.category = normalize_category(item.category);
+                existing.tags = normalize_tags(Some(item.tags));
+                existing.updated_at = now;
+                existing.revision += 1;
+
+                template_revision::record_revision(existing)?;
+                summary.overwritten += 1;
+            }
+            (existing, _) => {
+                // 无冲突时保留原 ID，便于之后再次导入时识别为同一模板
+                let id = match (&existing, item.id) {
+                    (None, Some(id)) => id,
+                    _ => Uuid::new_v4().to_string(),
+                };
+                let created = CommentTemplate {
+                    id,
+                    name,
+                    content: item.content,
+                    category: normalize_category(item.category),
+                    tags: normalize_tags(Some(item.tags)),
+                    created_at: now,
+                    updated_at: now,
+                    last_used_at: None,
+                    revision: 1,
+                };
+
+                template_revision::record_revision(&created)?;
+                templates.push(created);
+                if existing.is_some() {
+                    summary.duplicated += 1;
+                } else {
+                    summary.created += 1;
+                }
+            }
+        }
+    }
+
+    template::save_templates(&templates)?;
+
+    log::info!(
+        "模板导入完成: 新增={}, 覆盖={}, 另存={}, 跳过={}, 错误={}",
+        summary.created,
+        summary.overwritten,
+        summary.duplicated,
+        summary.skipped,
+        summary.errors.len()
+    );
+    Ok(summary)
+}
+
+/// 汇总单个模板的批次历史
+fn summarize_batches(
+    template_id: String,
+    name: Option<String>,
+    batches: &[&BatchStatus],
+) -> TemplateStats {
+    let mut success = 0;
+    let mut failed = 0;
+    let mut codes: HashMap<Option<i32>, u32> = HashMap::new();
+
+    for task in batches.iter().flat_map(|b| b.tasks.iter()) {
+        match task.status {
+            TaskStatus::Success => success += 1,
+            TaskStatus::Failed => {
+                failed += 1;
+                *codes.entry(task.error_code).or_default() += 1;
+            }
+            _ => {}
+        }
+    }
+
+    let mut failure_codes: Vec<FailureCodeCount> = codes
+        .into_iter()
+        .map(|(code, count)| FailureCodeCount {
+            code,
+            message: code
+                .and_then(error_code::lookup)
+                .map(|info| info.localized_message().to_string()),
+            count,
+        })
+        .collect();
+    failure_codes.sort_by(|a, b| b.count.cmp(&a.count).then(a.code.cmp(&b.code)));
+
+    let sent = success + failed;
+    TemplateStats {
+        template_id,
+        name,
+        batches: batches.len() as u32,
+        success,
+        failed,
+        success_rate: if sent > 0 {
+            success as f64 / sent as f64
+        } else {
+            0.0
+        },
+        failure_codes,
+        last_used_at: batches.iter().map(|b| b.created_at).max(),
+        engagement: None,
+    }
+}
+
+/// 查询模板最近发送的评论的平均互动数据
+async fn sample_engagement(batches: &[&BatchStatus]) -> TemplateEngagement {
+    let mut sent: Vec<(i64, u64, u64)> = batches
+        .iter()
+        .flat_map(|b| b.tasks.iter())
+        .filter(|t| t.status == TaskStatus::Success)
+        .filter_map(|t| Some((t.completed_at.unwrap_or_default(), t.video.aid, t.rpid?)))
+        .collect();
+    sent.sort_by_key(|(completed_at, _, _)| std::cmp::Reverse(*completed_at));
+
+    let mut sampled = 0;
+    let mut likes = 0;
+    let mut replies = 0;
+    for (_, aid, rpid) in sent.into_iter().take(ENGAGEMENT_SAMPLE_SIZE) {
+        match comment::get_reply_engagement(aid, rpid).await {
+            Ok(Some(engagement)) => {
+                sampled += 1;
+                likes += engagement.likes;
+                replies += engagement.replies;
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("获取评论互动数据失败: rpid={}, {}", rpid, e),
+        }
+    }
+
+    let average = |total: u64| {
+        if sampled > 0 {
+            total as f64 / sampled as f64
+        } else {
+            0.0
+        }
+    };
+    TemplateEngagement {
+        sampled,
+        avg_likes: average(likes),
+        avg_replies: average(replies),
+    }
+}
+
+/// 获取模板使用统计 (template_id 为空时统计全部模板)
+///
+/// with_engagement 为 true 时会逐条查询最近评论的点赞和回复数，耗时较长
+#[tauri::command]
+pub async fn get_template_stats(
+    template_id: Option<String>,
+    with_engagement: Option<bool>,
+) -> Result<Vec<TemplateStats>, String> {
+    let history: Vec<BatchStatus> = batch_history()?
+        .into_iter()
+        .filter(|b| !b.dry_run && !matches!(b.state, BatchState::Draft | BatchState::Approved))
+        .filter(|b| b.template_id.is_some())
+        .collect();
+
+    let names: HashMap<String, String> = template::load_templates()?
+        .into_iter()
+        .map(|t| (t.id, t.name))
+        .collect();
+
+    let mut grouped: BTreeMap<String, Vec<&BatchStatus>> = BTreeMap::new();
+    // 未使用过的模板也列出
+    for id in names.keys() {
+        grouped.entry(id.clone()).or_default();
+    }
+    for batch in &history {
+        if let Some(id) = &batch.template_id {
+            grouped.entry(id.clone()).or_default().push(batch);
+        }
+    }
+    if let Some(id) = &template_id {
+        grouped.retain(|key, _| key == id);
+    }
+
+    let mut stats = Vec::with_capacity(grouped.len());
+    for (id, batches) in grouped {
+        let name = names.get(&id).cloned();
+        let mut entry = summarize_batches(id, name, &batches);
+        if with_engagement.unwrap_or(false) {
+            entry.engagement = Some(sample_engagement(&batches).await);
+        }
+        stats.push(entry);
+    }
+
+    stats.sort_by_key(|s| std::cmp::Reverse(s.batches));
+    Ok(stats)
+}
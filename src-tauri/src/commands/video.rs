@@ -0,0 +1,32 @@
+use crate::api::video;
+use crate::i18n::{self, Msg};
+use crate::models::ResolvedVideo;
+use crate::utils::video_url::{parse_video_link, VideoLink};
+
+/// 解析粘贴的视频链接、分享文本或 BV/AV 号，返回视频信息
+///
+/// b23.tv 短链接会先请求并跟随跳转，再解析跳转后的地址
+#[tauri::command]
+pub async fn resolve_video(input: String) -> Result<ResolvedVideo, String> {
+    let invalid = || i18n::tf(Msg::InvalidVideoLink, &[&input.trim()]);
+
+    let link = match parse_video_link(&input).ok_or_else(invalid)? {
+        VideoLink::ShortLink(url) => {
+            let target = video::resolve_short_link(&url)
+                .await
+                .map_err(|e| e.to_user_message())?;
+            parse_video_link(&target).ok_or_else(invalid)?
+        }
+        link => link,
+    };
+
+    // 跳转后仍为短链接说明无法解析
+    let VideoLink::Video { aid, page } = link else {
+        return Err(invalid());
+    };
+
+    let video = video::get_video(aid)
+        .await
+        .map_err(|e| e.to_user_message())?;
+    Ok(ResolvedVideo { video, page })
+}
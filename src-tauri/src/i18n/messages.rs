@@ -0,0 +1,416 @@
+use super::Locale;
+
+/// 文案编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // 错误信息
+    NetworkError,
+    NotLoggedIn,
+    RateLimited,
+    QrCodeExpired,
+    CommentFailed,
+    SensitiveContent,
+    ParseError,
+    IoError,
+    UnknownApiError,
+
+    // 登录状态
+    LoginConfirmed,
+    LoginQrExpired,
+    LoginScanned,
+    LoginWaiting,
+
+    // 批量任务
+    BatchNotFound,
+    BatchActive,
+    BatchAbortedAccountError,
+    TaskInterrupted,
+    SkipDuplicate,
+    SkipQuotaExceeded,
+    SkipAuthorBlocked,
+    SkipVideoBlocked,
+    SkipNotAllowlisted,
+
+    // 批次审核
+    ReviewerRequired,
+    ApproverIsCreator,
+    BatchNotDraft,
+    BatchNotApproved,
+    TaskNotFound,
+    NoApprovedTasks,
+    InvalidTimeWindow,
+
+    // 搜索
+    InvalidPubtimeRange,
+    KeywordRequired,
+    CollectMaxItemsRange,
+    InvalidVideoLink,
+
+    // 视频筛选
+    InvalidFilterRange,
+    InvalidFilterRegex,
+    FilterPresetNotFound,
+    FilterPresetNameRequired,
+    FilterPlayTooLow,
+    FilterPlayTooHigh,
+    FilterDanmakuTooLow,
+    FilterDanmakuTooHigh,
+    FilterDurationUnknown,
+    FilterDurationTooShort,
+    FilterDurationTooLong,
+    FilterTooOld,
+    FilterTitleMissingKeyword,
+    FilterTitleExcluded,
+    FilterTitleRegexMismatch,
+    FilterAuthorBlocked,
+    FilterAuthorNotAllowed,
+
+    // 屏蔽/允许名单
+    InvalidAccessListValue,
+    AccessListEntryNotFound,
+
+    // 设置
+    InvalidSettingRange,
+    InvalidUserAgent,
+
+    // 数据备份
+    BackupPasswordRequired,
+    BackupWrongPassword,
+    BackupInvalidFile,
+    BackupUnsupportedVersion,
+    BackupBatchActive,
+
+    // 模板渲染
+    TemplateUnknownPlaceholder,
+    TemplateUnclosedBrace,
+    TemplateMissingUser,
+    TemplateInvalidFormat,
+    TemplateContentRequired,
+    TemplateNotFound,
+    TemplateRevisionNotFound,
+    TemplateNameRequired,
+    TemplateUnnamed,
+    TemplateCsvTagSeparator,
+    TemplateCsvRowInvalid,
+    IssueEmpty,
+    IssueTooLong,
+    IssueBannedWord,
+    IssueUnsupportedChar,
+
+    // 评论日志
+    LogSendComment,
+    LogBatchStart,
+    LogBatchExecute,
+    LogBatchCancelled,
+    LogTaskExecute,
+    LogTaskRetry,
+    LogPrecheckRetry,
+    LogTaskSkipped,
+    LogDryRunTask,
+    LogBatchAbort,
+    LogBatchScheduled,
+    LogOutsideWindow,
+    LogBatchFinished,
+    LogCancelBatch,
+    LogDraftCreated,
+    LogBatchApproved,
+    LogExecuteApproved,
+}
+
+impl Msg {
+    /// 获取指定语言下的文案
+    pub fn text(self, locale: Locale) -> &'static str {
+        let (zh, en) = self.pair();
+        match locale {
+            Locale::ZhCn => zh,
+            Locale::En => en,
+        }
+    }
+
+    /// 文案表: (简体中文, English)
+    fn pair(self) -> (&'static str, &'static str) {
+        match self {
+            Self::NetworkError => ("网络请求失败: {}", "Network request failed: {}"),
+            Self::NotLoggedIn => ("请先登录", "Please log in first"),
+            Self::RateLimited => (
+                "操作过于频繁，请稍等几秒后重试",
+                "Too many requests, please wait a few seconds and retry",
+            ),
+            Self::QrCodeExpired => ("二维码已过期，请刷新", "QR code expired, please refresh"),
+            Self::CommentFailed => ("评论失败: {}", "Failed to post comment: {}"),
+            Self::SensitiveContent => (
+                "评论内容包含本地敏感词: {}",
+                "Comment contains locally banned words: {}",
+            ),
+            Self::ParseError => ("数据解析失败: {}", "Failed to parse response: {}"),
+            Self::IoError => ("IO错误: {}", "IO error: {}"),
+            Self::UnknownApiError => ("错误 {}: {}", "Error {}: {}"),
+
+            Self::LoginConfirmed => ("登录成功", "Logged in"),
+            Self::LoginQrExpired => ("二维码已过期", "QR code expired"),
+            Self::LoginScanned => (
+                "已扫码，请在手机上确认",
+                "Scanned, please confirm on your phone",
+            ),
+            Self::LoginWaiting => ("等待扫码", "Waiting for scan"),
+
+            Self::BatchNotFound => ("批次不存在", "Batch not found"),
+            Self::BatchActive => (
+                "批次正在执行或已排期，请先取消",
+                "The batch is running or scheduled, cancel it first",
+            ),
+            Self::BatchAbortedAccountError => (
+                "账号异常，批量任务已中止",
+                "Batch aborted due to an account error",
+            ),
+            Self::TaskInterrupted => (
+                "程序退出时该评论正在发送，结果未知，为避免重复评论不再重试",
+                "The app exited while this comment was being posted; the result is unknown, so it will not be retried to avoid a duplicate",
+            ),
+            Self::SkipDuplicate => (
+                "该视频已评论过或在本批次中重复",
+                "Already commented on this video or duplicated in this batch",
+            ),
+            Self::SkipQuotaExceeded => (
+                "已达到每日评论上限 ({} 条)",
+                "Daily comment quota reached ({} comments)",
+            ),
+            Self::SkipAuthorBlocked => ("UP主在屏蔽名单中 ({})", "Author is blocklisted ({})"),
+            Self::SkipVideoBlocked => ("视频在屏蔽名单中 ({})", "Video is blocklisted ({})"),
+            Self::SkipNotAllowlisted => (
+                "已开启仅允许名单模式，该视频和UP主均不在允许名单中",
+                "Allowlist-only mode is on and neither the video nor its author is allowlisted",
+            ),
+
+            Self::ReviewerRequired => ("请填写创建人或审核人", "Creator or approver is required"),
+            Self::ApproverIsCreator => (
+                "审核人不能与创建人相同",
+                "The approver must be different from the creator",
+            ),
+            Self::BatchNotDraft => (
+                "只有草稿状态的批次可以审核",
+                "Only draft batches can be reviewed",
+            ),
+            Self::BatchNotApproved => (
+                "批次尚未审核通过，无法执行",
+                "The batch has not been approved yet",
+            ),
+            Self::TaskNotFound => ("任务不存在", "Task not found"),
+            Self::NoApprovedTasks => (
+                "没有保留的任务，无法通过审核",
+                "No tasks left to approve",
+            ),
+
+            Self::InvalidTimeWindow => (
+                "时间段的开始和结束时间不能相同",
+                "A time window must not start and end at the same time",
+            ),
+
+            Self::InvalidPubtimeRange => (
+                "发布时间的开始时间不能晚于结束时间",
+                "The publish date range must not start after it ends",
+            ),
+
+            Self::KeywordRequired => ("请输入搜索关键词", "Please enter a search keyword"),
+            Self::CollectMaxItemsRange => (
+                "收集数量应在 1 到 {} 之间",
+                "The number of videos to collect must be between 1 and {}",
+            ),
+            Self::InvalidVideoLink => (
+                "无法识别的视频链接或编号: {}",
+                "Unrecognized video link or id: {}",
+            ),
+
+            Self::InvalidFilterRange => (
+                "筛选条件 {} 的下限不能大于上限",
+                "The lower bound of filter {} must not exceed the upper bound",
+            ),
+            Self::InvalidFilterRegex => (
+                "无效的正则表达式 \"{}\": {}",
+                "Invalid regular expression \"{}\": {}",
+            ),
+            Self::FilterPresetNotFound => ("筛选预设不存在", "Filter preset not found"),
+            Self::FilterPresetNameRequired => (
+                "请填写筛选预设名称",
+                "Filter preset name is required",
+            ),
+            Self::FilterPlayTooLow => ("播放量低于 {}", "Fewer than {} plays"),
+            Self::FilterPlayTooHigh => ("播放量高于 {}", "More than {} plays"),
+            Self::FilterDanmakuTooLow => ("弹幕数低于 {}", "Fewer than {} danmaku"),
+            Self::FilterDanmakuTooHigh => ("弹幕数高于 {}", "More than {} danmaku"),
+            Self::FilterDurationUnknown => (
+                "无法解析视频时长: {}",
+                "Cannot parse video duration: {}",
+            ),
+            Self::FilterDurationTooShort => (
+                "视频时长短于 {} 秒",
+                "Shorter than {} seconds",
+            ),
+            Self::FilterDurationTooLong => ("视频时长超过 {} 秒", "Longer than {} seconds"),
+            Self::FilterTooOld => ("发布时间超过 {} 天", "Published more than {} days ago"),
+            Self::FilterTitleMissingKeyword => (
+                "标题不包含指定的关键词",
+                "Title contains none of the required keywords",
+            ),
+            Self::FilterTitleExcluded => (
+                "标题包含排除的关键词: {}",
+                "Title contains an excluded keyword: {}",
+            ),
+            Self::FilterTitleRegexMismatch => (
+                "标题不匹配正则表达式",
+                "Title does not match the regular expression",
+            ),
+            Self::FilterAuthorBlocked => (
+                "UP主在排除列表中: {}",
+                "Author is in the blocklist: {}",
+            ),
+            Self::FilterAuthorNotAllowed => (
+                "UP主不在允许列表中: {}",
+                "Author is not in the allowlist: {}",
+            ),
+
+            Self::InvalidAccessListValue => (
+                "无效的名单条目: {} (UP主请填写 mid，视频请填写 aid 或 BV 号)",
+                "Invalid entry: {} (use the mid for authors, the aid or BV id for videos)",
+            ),
+            Self::AccessListEntryNotFound => ("名单条目不存在", "List entry not found"),
+
+            Self::InvalidSettingRange => (
+                "设置项 {} 的取值应在 {} 到 {} 之间",
+                "Setting {} must be between {} and {}",
+            ),
+            Self::InvalidUserAgent => (
+                "User-Agent 不能为空，且只能包含可见 ASCII 字符",
+                "User-Agent must not be empty and may only contain printable ASCII characters",
+            ),
+
+            Self::BackupPasswordRequired => (
+                "备份登录凭证时需要设置密码",
+                "A password is required to include the login credential",
+            ),
+            Self::BackupWrongPassword => (
+                "密码错误，无法解密备份中的登录凭证",
+                "Wrong password, cannot decrypt the login credential in the backup",
+            ),
+            Self::BackupInvalidFile => ("备份文件无效: {}", "Invalid backup file: {}"),
+            Self::BackupUnsupportedVersion => (
+                "备份文件版本 {} 高于当前程序支持的版本 {}，请升级程序",
+                "Backup version {} is newer than the supported version {}, please upgrade",
+            ),
+            Self::BackupBatchActive => (
+                "有正在执行或已排期的批次，请先取消后再导入备份",
+                "Cancel running or scheduled batches before importing a backup",
+            ),
+
+            Self::TemplateUnknownPlaceholder => ("未知的占位符: {{}}", "Unknown placeholder: {{}}"),
+            Self::TemplateUnclosedBrace => (
+                "模板中存在未闭合的 {",
+                "Template contains an unclosed {",
+            ),
+            Self::TemplateMissingUser => (
+                "占位符 {{}} 需要登录后才能使用",
+                "Placeholder {{}} requires a logged-in user",
+            ),
+            Self::TemplateInvalidFormat => (
+                "占位符 {{}} 的格式无效: {}",
+                "Invalid format for placeholder {{}}: {}",
+            ),
+
+            Self::TemplateContentRequired => (
+                "请提供模板ID或模板内容",
+                "Either a template id or template content is required",
+            ),
+            Self::TemplateNotFound => ("模板不存在", "Template not found"),
+            Self::TemplateRevisionNotFound => ("修订版本不存在", "Template revision not found"),
+            Self::TemplateNameRequired => ("模板名称不能为空", "Template name is required"),
+            Self::TemplateUnnamed => ("(未命名)", "(unnamed)"),
+            Self::TemplateCsvTagSeparator => (
+                "模板 \"{}\" 的标签 \"{}\" 包含分号，无法导出为 CSV",
+                "Template \"{}\" has tag \"{}\" containing a semicolon, which CSV export cannot represent",
+            ),
+            Self::TemplateCsvRowInvalid => (
+                "第 {} 行解析失败: {}",
+                "Failed to parse row {}: {}",
+            ),
+            Self::IssueEmpty => ("渲染后内容为空", "Content is empty after rendering"),
+            Self::IssueTooLong => (
+                "渲染后共 {} 字，超过评论上限 {} 字",
+                "Rendered content has {} characters, exceeding the {} character limit",
+            ),
+            Self::IssueBannedWord => ("包含违禁词: {}", "Contains banned word: {}"),
+            Self::IssueUnsupportedChar => (
+                "包含不支持的字符: {}",
+                "Contains unsupported character: {}",
+            ),
+
+            Self::LogSendComment => (
+                "📝 Command: 发送单条评论 aid={}",
+                "📝 Command: send comment aid={}",
+            ),
+            Self::LogBatchStart => (
+                "🚀 Command: 启动批量评论任务 batch_id={}, 视频数={}",
+                "🚀 Command: start batch batch_id={}, videos={}",
+            ),
+            Self::LogBatchExecute => (
+                "📋 批量任务开始执行: batch_id={}, 任务数={}",
+                "📋 Batch started: batch_id={}, tasks={}",
+            ),
+            Self::LogBatchCancelled => (
+                "⏹️ 批量任务被取消: batch_id={}, 已完成={}/{}",
+                "⏹️ Batch cancelled: batch_id={}, done={}/{}",
+            ),
+            Self::LogTaskExecute => (
+                "▶️ 执行任务 [{}/{}]: aid={}, 标题=\"{}\"",
+                "▶️ Running task [{}/{}]: aid={}, title=\"{}\"",
+            ),
+            Self::LogTaskRetry => (
+                "🔁 评论失败但可重试: aid={}, 第 {}/{} 次重试",
+                "🔁 Retryable comment failure: aid={}, retry {}/{}",
+            ),
+            Self::LogPrecheckRetry => (
+                "🔁 评论区检查失败但可重试: aid={}, 第 {}/{} 次重试",
+                "🔁 Retryable comment-area check failure: aid={}, retry {}/{}",
+            ),
+            Self::LogTaskSkipped => (
+                "⏭️ 预检未通过，跳过: aid={}, 原因={}",
+                "⏭️ Pre-check failed, skipped: aid={}, reason={}",
+            ),
+            Self::LogDryRunTask => (
+                "🧪 演练模式，将发送: aid={}, 内容=\"{}\"",
+                "🧪 Dry run, would post: aid={}, content=\"{}\"",
+            ),
+            Self::LogBatchAbort => (
+                "🛑 遇到账号级错误，中止批量任务: batch_id={}, 已完成={}/{}",
+                "🛑 Account-level error, aborting batch: batch_id={}, done={}/{}",
+            ),
+            Self::LogBatchScheduled => (
+                "⏰ 批次已排期: batch_id={}, 开始时间={}",
+                "⏰ Batch scheduled: batch_id={}, start_at={}",
+            ),
+            Self::LogOutsideWindow => (
+                "🌙 不在允许的时间段内，等待至 {}: batch_id={}",
+                "🌙 Outside allowed time windows, waiting until {}: batch_id={}",
+            ),
+            Self::LogBatchFinished => (
+                "🏁 批量任务完成: batch_id={}, 成功={}, 失败={}, 总计={}",
+                "🏁 Batch finished: batch_id={}, success={}, failed={}, total={}",
+            ),
+            Self::LogCancelBatch => (
+                "⏹️ Command: 取消批量任务 batch_id={}",
+                "⏹️ Command: cancel batch batch_id={}",
+            ),
+            Self::LogDraftCreated => (
+                "📝 Command: 创建批次草稿 batch_id={}, 视频数={}, 创建人={}",
+                "📝 Command: create draft batch batch_id={}, videos={}, created_by={}",
+            ),
+            Self::LogBatchApproved => (
+                "✅ Command: 批次审核通过 batch_id={}, 保留任务={}, 审核人={}",
+                "✅ Command: batch approved batch_id={}, kept_tasks={}, approver={}",
+            ),
+            Self::LogExecuteApproved => (
+                "🚀 Command: 执行已审核批次 batch_id={}",
+                "🚀 Command: execute approved batch batch_id={}",
+            ),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// 多语言模块
+
+mod messages;
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+pub use messages::Msg;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en")]
+    En,
+}
+
+/// 当前语言
+static CURRENT_LOCALE: Lazy<RwLock<Locale>> = Lazy::new(|| RwLock::new(Locale::default()));
+
+/// 获取当前语言
+pub fn current_locale() -> Locale {
+    *CURRENT_LOCALE.read()
+}
+
+/// 切换当前语言
+pub fn set_locale(locale: Locale) {
+    *CURRENT_LOCALE.write() = locale;
+}
+
+/// 获取当前语言下的文案
+pub fn t(msg: Msg) -> &'static str {
+    msg.text(current_locale())
+}
+
+/// 文案中 `{}` 占位符的个数
+fn placeholder_count(text: &str) -> usize {
+    text.matches("{}").count()
+}
+
+/// 获取当前语言下的文案，并按顺序替换其中的 `{}` 占位符
+///
+/// 参数个数与占位符个数不一致时记录日志 (调试构建下直接断言失败)
+pub fn tf(msg: Msg, args: &[&dyn Display]) -> String {
+    let text = t(msg);
+    let expected = placeholder_count(text);
+    if expected != args.len() {
+        log::warn!(
+            "文案 {:?} 需要 {} 个参数，实际传入 {} 个",
+            msg,
+            expected,
+            args.len()
+        );
+        debug_assert_eq!(expected, args.len(), "文案 {:?} 的参数个数不一致", msg);
+    }
+
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = text.split("{}").peekable();
+
+    while let Some(part) = parts.next() {
+        out.push_str(part);
+        if parts.peek().is_some() {
+            match args.next() {
+                Some(arg) => out.push_str(&arg.to_string()),
+                None => out.push_str("{}"),
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tf_replaces_placeholders_in_order() {
+        let text = tf(Msg::UnknownApiError, &[&-400, &"bad"]);
+        assert!(text.ends_with("-400: bad"), "{}", text);
+    }
+
+    #[test]
+    fn tf_keeps_literal_braces_around_placeholder() {
+        let text = tf(Msg::TemplateUnknownPlaceholder, &[&"foo"]);
+        assert!(text.ends_with("{foo}"), "{}", text);
+    }
+
+    #[test]
+    fn counts_placeholders() {
+        assert_eq!(placeholder_count(t(Msg::UnknownApiError)), 2);
+        // `{{}}` 中只有内层的 `{}` 是占位符
+        assert_eq!(placeholder_count(t(Msg::TemplateUnknownPlaceholder)), 1);
+        assert_eq!(placeholder_count(t(Msg::TemplateNotFound)), 0);
+    }
+}
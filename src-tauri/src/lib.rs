@@ -1,10 +1,17 @@
 // 模块声明
 mod api;
 mod commands;
+mod i18n;
 mod models;
+mod moderation;
+mod render;
 mod storage;
+mod utils;
+mod video_filter;
 
-use commands::{auth, comment, search, template};
+use commands::{
+    access_list, auth, comment, data, filter, review, search, sensitive, settings, template, video,
+};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -19,9 +26,20 @@ pub fn run() {
                 )?;
             }
 
+            // 打开数据库 (首次运行时迁移旧版 JSON 数据)
+            if let Err(e) = storage::db::init() {
+                log::error!("初始化数据库失败: {}", e);
+            }
+
+            // 应用已保存的语言设置
+            i18n::set_locale(storage::settings::current().locale);
+
             // 初始化已保存的登录凭证
             api::login::init_credential();
 
+            // 加载已保存的批次
+            comment::init_batches();
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -33,18 +51,59 @@ pub fn run() {
             auth::check_login_valid,
             // 搜索命令
             search::search_videos,
+            search::collect_search,
+            search::cancel_collect,
+            video::resolve_video,
+            filter::get_filter_presets,
+            filter::save_filter_preset,
+            filter::delete_filter_preset,
+            filter::apply_video_filter,
+            access_list::get_access_list,
+            access_list::add_access_list_entry,
+            access_list::remove_access_list_entry,
             // 评论命令
             comment::send_comment,
             comment::batch_send_comments,
             comment::get_batch_status,
             comment::cancel_batch,
             comment::clear_batch,
+            comment::delete_batch,
             comment::get_comment_interval,
+            comment::get_error_codes,
+            // 批次审核命令
+            review::create_draft_batch,
+            review::list_batches,
+            review::review_task,
+            review::approve_batch,
+            review::execute_batch,
             // 模板命令
             template::get_templates,
             template::create_template,
             template::update_template,
             template::delete_template,
+            template::preview_template,
+            template::get_template_categories,
+            template::get_template_revisions,
+            template::rollback_template,
+            template::export_templates,
+            template::import_templates,
+            template::get_template_stats,
+            // 敏感词命令
+            sensitive::get_sensitive_words,
+            sensitive::set_sensitive_words,
+            sensitive::import_sensitive_words,
+            sensitive::check_sensitive_words,
+            // 设置命令
+            settings::get_locale,
+            settings::set_locale,
+            settings::get_settings,
+            settings::update_settings,
+            // 数据管理命令
+            data::get_storage_issues,
+            data::clear_storage_issues,
+            data::get_data_location,
+            data::export_backup,
+            data::import_backup,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
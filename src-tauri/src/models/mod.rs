@@ -1,5 +1,9 @@
 // 数据模型模块
 
+pub mod schedule;
+pub mod settings;
 pub mod types;
 
+pub use schedule::*;
+pub use settings::*;
 pub use types::*;
@@ -0,0 +1,263 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Msg};
+
+/// 夏令时切换造成的本地时间空缺最多顺延的小时数
+const MAX_GAP_HOURS: i64 = 3;
+
+/// 允许执行的时间段 (本地时间，结束时间早于开始时间表示跨越午夜)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// 指定时刻是否在时间段内
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// 批次执行计划
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchSchedule {
+    /// 开始时间 (RFC 3339，带时区偏移)，为空表示立即开始
+    #[serde(default)]
+    pub start_at: Option<DateTime<FixedOffset>>,
+    /// 允许执行的时间段，为空表示不限制
+    #[serde(default)]
+    pub windows: Vec<TimeWindow>,
+}
+
+impl BatchSchedule {
+    /// 校验计划是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        if self.windows.iter().any(|w| w.start == w.end) {
+            return Err(i18n::t(Msg::InvalidTimeWindow).to_string());
+        }
+        Ok(())
+    }
+
+    /// 开始时间 (UTC)
+    pub fn start_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.start_at.map(|t| t.with_timezone(&Utc))
+    }
+
+    /// 下一个允许执行的时刻 (当前已允许则返回 now)
+    pub fn next_allowed(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.next_allowed_in(now, &Local)
+    }
+
+    /// 按指定时区判断时刻是否允许执行
+    fn is_allowed_in<Tz: TimeZone>(&self, now: DateTime<Utc>, tz: &Tz) -> bool {
+        let time = now.with_timezone(tz).time();
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(time))
+    }
+
+    /// 按指定时区计算下一个允许执行的时刻
+    fn next_allowed_in<Tz: TimeZone>(&self, now: DateTime<Utc>, tz: &Tz) -> DateTime<Utc> {
+        if self.is_allowed_in(now, tz) {
+            return now;
+        }
+
+        let local_now = now.with_timezone(tz).naive_local();
+        self.windows
+            .iter()
+            .filter_map(|w| {
+                let today = local_now.date().and_time(w.start);
+                let candidate = if today > local_now {
+                    today
+                } else {
+                    today + Duration::days(1)
+                };
+                resolve_local(tz, candidate)
+            })
+            .min()
+            .unwrap_or(now)
+    }
+}
+
+/// 本地时间转为 UTC
+///
+/// 夏令时切换导致该本地时间不存在时逐小时顺延到第一个存在的时刻，重复时取较早的时刻
+fn resolve_local<Tz: TimeZone>(tz: &Tz, time: NaiveDateTime) -> Option<DateTime<Utc>> {
+    (0..=MAX_GAP_HOURS).find_map(|hours| {
+        tz.from_local_datetime(&(time + Duration::hours(hours)))
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{LocalResult, NaiveDate};
+
+    use super::*;
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn window(start: NaiveTime, end: NaiveTime) -> BatchSchedule {
+        BatchSchedule {
+            start_at: None,
+            windows: vec![TimeWindow { start, end }],
+        }
+    }
+
+    /// UTC+8 下的 2024-03-01 本地时间
+    fn local(h: u32, m: u32) -> DateTime<Utc> {
+        tz().with_ymd_and_hms(2024, 3, 1, h, m, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn tz() -> FixedOffset {
+        FixedOffset::east_opt(8 * 3600).unwrap()
+    }
+
+    #[test]
+    fn daytime_window_contains() {
+        let w = TimeWindow {
+            start: hm(10, 0),
+            end: hm(22, 0),
+        };
+        assert!(w.contains(hm(10, 0)));
+        assert!(w.contains(hm(21, 59)));
+        assert!(!w.contains(hm(22, 0)));
+        assert!(!w.contains(hm(9, 59)));
+    }
+
+    #[test]
+    fn overnight_window_contains() {
+        let w = TimeWindow {
+            start: hm(22, 0),
+            end: hm(2, 0),
+        };
+        assert!(w.contains(hm(23, 30)));
+        assert!(w.contains(hm(0, 0)));
+        assert!(w.contains(hm(1, 59)));
+        assert!(!w.contains(hm(2, 0)));
+        assert!(!w.contains(hm(12, 0)));
+    }
+
+    #[test]
+    fn next_allowed_inside_window_is_now() {
+        let schedule = window(hm(10, 0), hm(22, 0));
+        let now = local(12, 0);
+        assert_eq!(schedule.next_allowed_in(now, &tz()), now);
+    }
+
+    #[test]
+    fn next_allowed_waits_for_todays_window() {
+        let schedule = window(hm(10, 0), hm(22, 0));
+        assert_eq!(schedule.next_allowed_in(local(8, 0), &tz()), local(10, 0));
+    }
+
+    #[test]
+    fn next_allowed_rolls_over_to_tomorrow() {
+        let schedule = window(hm(10, 0), hm(22, 0));
+        let expected = local(10, 0) + Duration::days(1);
+        assert_eq!(schedule.next_allowed_in(local(23, 0), &tz()), expected);
+    }
+
+    #[test]
+    fn next_allowed_overnight_window() {
+        let schedule = window(hm(22, 0), hm(2, 0));
+        assert_eq!(schedule.next_allowed_in(local(1, 0), &tz()), local(1, 0));
+        assert_eq!(schedule.next_allowed_in(local(3, 0), &tz()), local(22, 0));
+    }
+
+    #[test]
+    fn next_allowed_picks_earliest_window() {
+        let schedule = BatchSchedule {
+            start_at: None,
+            windows: vec![
+                TimeWindow {
+                    start: hm(20, 0),
+                    end: hm(22, 0),
+                },
+                TimeWindow {
+                    start: hm(9, 0),
+                    end: hm(11, 0),
+                },
+            ],
+        };
+        assert_eq!(schedule.next_allowed_in(local(12, 0), &tz()), local(20, 0));
+    }
+
+    /// 模拟夏令时的时区: 2024-03-31 01:00 UTC 起由 UTC+1 切换为 UTC+2，
+    /// 当天本地时间 02:00-03:00 不存在
+    #[derive(Debug, Clone, Copy)]
+    struct DstZone;
+
+    impl DstZone {
+        fn switch_at() -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(2024, 3, 31)
+                .unwrap()
+                .and_hms_opt(1, 0, 0)
+                .unwrap()
+        }
+
+        fn offset(summer: bool) -> FixedOffset {
+            FixedOffset::east_opt(if summer { 7200 } else { 3600 }).unwrap()
+        }
+    }
+
+    impl TimeZone for DstZone {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            DstZone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let winter = *local - Duration::hours(1) < Self::switch_at();
+            let summer = *local - Duration::hours(2) >= Self::switch_at();
+            match (winter, summer) {
+                (true, false) => LocalResult::Single(Self::offset(false)),
+                (false, true) => LocalResult::Single(Self::offset(true)),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset(*utc >= Self::switch_at())
+        }
+    }
+
+    #[test]
+    fn next_allowed_skips_dst_gap() {
+        // 窗口从不存在的 02:30 开始，应顺延到 03:30 (UTC 01:30)，而不是忽略该窗口
+        let schedule = window(hm(2, 30), hm(6, 0));
+        let now = Utc.with_ymd_and_hms(2024, 3, 30, 23, 0, 0).unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 3, 31, 1, 30, 0).unwrap();
+        assert_eq!(schedule.next_allowed_in(now, &DstZone), expected);
+        assert!(schedule.is_allowed_in(expected, &DstZone));
+    }
+
+    #[test]
+    fn empty_windows_always_allowed() {
+        let schedule = BatchSchedule::default();
+        assert!(schedule.is_allowed_in(local(3, 0), &tz()));
+    }
+
+    #[test]
+    fn equal_start_and_end_is_invalid() {
+        assert!(window(hm(10, 0), hm(10, 0)).validate().is_err());
+    }
+}
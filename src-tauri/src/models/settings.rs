@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Locale, Msg};
+
+/// 默认 User-Agent
+pub const DEFAULT_USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+     (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// 应用设置 (缺少的字段使用默认值)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 界面及日志语言
+    pub locale: Locale,
+    /// 评论发送间隔 (秒)
+    pub comment_interval_secs: u64,
+    /// 批量任务中单条评论可重试错误的最大重试次数
+    pub task_max_retries: u32,
+    /// 最近 24 小时内最多发送的评论数 (批量发送和演练都按此跳过超出的任务)
+    pub daily_comment_quota: usize,
+    /// 仅允许名单模式: 只评论允许名单中的 UP主 或视频
+    pub allowlist_only: bool,
+    /// 搜索请求最多尝试次数
+    pub search_max_attempts: u32,
+    /// 搜索请求失败后的重试间隔 (毫秒)
+    pub search_retry_delay_ms: u64,
+    /// 多页搜索时每页之间的间隔 (毫秒)
+    pub collect_page_delay_ms: u64,
+    /// HTTP 请求超时时间 (秒)
+    pub request_timeout_secs: u64,
+    /// 请求使用的 User-Agent
+    pub user_agent: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            comment_interval_secs: 5,
+            task_max_retries: 2,
+            daily_comment_quota: 100,
+            allowlist_only: false,
+            search_max_attempts: 5,
+            search_retry_delay_ms: 500,
+            collect_page_delay_ms: 1500,
+            request_timeout_secs: 30,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+        }
+    }
+}
+
+/// 校验数值设置项是否在范围内
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    name: &str,
+    value: T,
+    min: T,
+    max: T,
+) -> Result<(), String> {
+    if value < min || value > max {
+        return Err(i18n::tf(Msg::InvalidSettingRange, &[&name, &min, &max]));
+    }
+    Ok(())
+}
+
+impl Settings {
+    /// 逐项校验，返回无效设置项的名称 (与字段名一致) 和错误信息
+    fn check_fields(&self) -> Vec<(&'static str, String)> {
+        let mut problems = Vec::new();
+        let mut check = |name: &'static str, value: u64, min: u64, max: u64| {
+            if let Err(e) = check_range(name, value, min, max) {
+                problems.push((name, e));
+            }
+        };
+        check("comment_interval_secs", self.comment_interval_secs, 1, 3600);
+        check("task_max_retries", self.task_max_retries.into(), 0, 10);
+        check(
+            "daily_comment_quota",
+            self.daily_comment_quota as u64,
+            1,
+            10_000,
+        );
+        check(
+            "search_max_attempts",
+            self.search_max_attempts.into(),
+            1,
+            10,
+        );
+        check(
+            "search_retry_delay_ms",
+            self.search_retry_delay_ms,
+            0,
+            60_000,
+        );
+        check(
+            "collect_page_delay_ms",
+            self.collect_page_delay_ms,
+            0,
+            60_000,
+        );
+        check("request_timeout_secs", self.request_timeout_secs, 1, 300);
+
+        // 作为请求头发送，只允许可见 ASCII 字符和空格
+        let user_agent = self.user_agent.trim();
+        if user_agent.is_empty() || !user_agent.chars().all(|c| c == ' ' || c.is_ascii_graphic()) {
+            problems.push(("user_agent", i18n::t(Msg::InvalidUserAgent).to_string()));
+        }
+
+        problems
+    }
+
+    /// 校验设置是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        match self.check_fields().into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// 无效设置项的名称 (与字段名一致)
+    pub fn invalid_fields(&self) -> Vec<&'static str> {
+        self.check_fields()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(Settings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn reports_invalid_fields() {
+        let settings = Settings {
+            comment_interval_secs: 0,
+            search_retry_delay_ms: u64::MAX,
+            user_agent: "bad\nagent".to_string(),
+            ..Settings::default()
+        };
+        assert!(settings.validate().is_err());
+        assert_eq!(
+            settings.invalid_fields(),
+            vec![
+                "comment_interval_secs",
+                "search_retry_delay_ms",
+                "user_agent"
+            ]
+        );
+    }
+}
@@ -1,5 +1,10 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use super::BatchSchedule;
+use crate::i18n::{self, Msg};
+
 /// 用户信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
@@ -54,6 +59,13 @@ pub enum LoginStatus {
     Error,
 }
 
+/// 文本中的高亮片段位置 (UTF-16 编码单元，左闭右开)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
 /// 视频信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoItem {
@@ -68,6 +80,17 @@ pub struct VideoItem {
     pub pubdate: i64,
     pub duration: String,
     pub description: String,
+    /// 标题中搜索关键词的高亮位置
+    #[serde(default)]
+    pub title_highlights: Vec<HighlightRange>,
+}
+
+/// 由链接或编号解析得到的视频
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedVideo {
+    pub video: VideoItem,
+    /// 链接中 `?p=` 指定的分 P
+    pub page: Option<u32>,
 }
 
 /// 搜索结果
@@ -79,14 +102,288 @@ pub struct SearchResult {
     pub items: Vec<VideoItem>,
 }
 
+/// 搜索排序方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchOrder {
+    /// 综合排序
+    #[default]
+    Totalrank,
+    /// 最多播放
+    Click,
+    /// 最新发布
+    Pubdate,
+    /// 最多弹幕
+    Dm,
+    /// 最多收藏
+    Stow,
+    /// 最多评论
+    Scores,
+}
+
+impl SearchOrder {
+    /// 搜索接口中的参数值
+    pub fn query_value(self) -> &'static str {
+        match self {
+            Self::Totalrank => "totalrank",
+            Self::Click => "click",
+            Self::Pubdate => "pubdate",
+            Self::Dm => "dm",
+            Self::Stow => "stow",
+            Self::Scores => "scores",
+        }
+    }
+}
+
+/// 视频时长筛选 (对应搜索接口的 `duration` 参数)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DurationFilter {
+    /// 不限
+    #[default]
+    #[serde(rename = "all")]
+    All,
+    /// 10 分钟以下
+    #[serde(rename = "under_10")]
+    Under10,
+    /// 10-30 分钟
+    #[serde(rename = "10_to_30")]
+    From10To30,
+    /// 30-60 分钟
+    #[serde(rename = "30_to_60")]
+    From30To60,
+    /// 60 分钟以上
+    #[serde(rename = "over_60")]
+    Over60,
+}
+
+impl DurationFilter {
+    /// 搜索接口中的参数值
+    pub fn query_value(self) -> u8 {
+        match self {
+            Self::All => 0,
+            Self::Under10 => 1,
+            Self::From10To30 => 2,
+            Self::From30To60 => 3,
+            Self::Over60 => 4,
+        }
+    }
+}
+
+/// 搜索筛选条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    /// 视频时长
+    #[serde(default)]
+    pub duration: DurationFilter,
+    /// 分区 ID (tid)，为空表示全部分区
+    #[serde(default)]
+    pub tid: Option<u32>,
+    /// 发布时间起 (Unix 时间戳，秒)
+    #[serde(default)]
+    pub pubtime_begin: Option<i64>,
+    /// 发布时间止 (Unix 时间戳，秒)
+    #[serde(default)]
+    pub pubtime_end: Option<i64>,
+}
+
+impl SearchFilters {
+    /// 校验发布时间范围
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(begin), Some(end)) = (self.pubtime_begin, self.pubtime_end) {
+            if begin > end {
+                return Err(i18n::t(Msg::InvalidPubtimeRange).to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 多页搜索进度事件 (每获取一页发送一次，结束时 `finished` 为 true)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchCollectEvent {
+    pub collect_id: String,
+    /// 本次事件对应的页码 (结束事件为最后获取的页码)
+    pub page: u32,
+    /// 本页新增的视频 (已按 aid 去重)
+    pub items: Vec<VideoItem>,
+    /// 累计收集的视频数
+    pub collected: usize,
+    pub finished: bool,
+    pub cancelled: bool,
+    /// 搜索失败时的错误信息 (已收集的结果仍然有效)
+    pub error: Option<String>,
+}
+
+/// 候选视频筛选规则 (未设置的条件不限制)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoFilterRules {
+    pub min_play: Option<u64>,
+    pub max_play: Option<u64>,
+    pub min_danmaku: Option<u64>,
+    pub max_danmaku: Option<u64>,
+    /// 视频时长下限 (秒)
+    pub min_duration_secs: Option<u64>,
+    /// 视频时长上限 (秒)
+    pub max_duration_secs: Option<u64>,
+    /// 发布时间距今最多天数
+    pub max_age_days: Option<u32>,
+    /// 标题需包含其中任一关键词 (不区分大小写)
+    pub title_include: Vec<String>,
+    /// 标题包含其中任一关键词时排除 (不区分大小写)
+    pub title_exclude: Vec<String>,
+    /// 标题需匹配的正则表达式
+    pub title_regex: Option<String>,
+    /// 排除的 UP主 (名称或 mid)
+    pub author_blocklist: Vec<String>,
+    /// 只保留的 UP主 (名称或 mid)，为空时不限制
+    pub author_allowlist: Vec<String>,
+}
+
+/// 已保存的筛选规则预设
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub id: String,
+    pub name: String,
+    pub rules: VideoFilterRules,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 被筛选规则排除的视频
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedVideo {
+    pub video: VideoItem,
+    pub reason: String,
+}
+
+/// 筛选结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FilterOutcome {
+    pub kept: Vec<VideoItem>,
+    pub rejected: Vec<RejectedVideo>,
+}
+
 /// 评论模板
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommentTemplate {
     pub id: String,
     pub name: String,
     pub content: String,
+    /// 分类
+    #[serde(default)]
+    pub category: Option<String>,
+    /// 标签
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: i64,
     pub updated_at: i64,
+    /// 最近一次用于批量评论的时间
+    #[serde(default)]
+    pub last_used_at: Option<i64>,
+    /// 当前修订版本号 (从 1 开始，每次更新加 1)
+    #[serde(default = "default_revision")]
+    pub revision: u32,
+}
+
+fn default_revision() -> u32 {
+    1
+}
+
+/// 模板修订版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRevision {
+    pub template_id: String,
+    pub revision: u32,
+    pub name: String,
+    pub content: String,
+    pub created_at: i64,
+}
+
+/// 模板分类与标签汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateCategories {
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// 模板排序方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateSort {
+    /// 最近使用的在前，从未使用的排在最后
+    LastUsed,
+    /// 最近更新的在前
+    UpdatedAt,
+    /// 最近创建的在前
+    CreatedAt,
+    /// 按名称
+    Name,
+}
+
+/// 模板筛选条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateFilter {
+    /// 在名称和内容中搜索，空格分隔的多个关键词需全部命中
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    /// 需包含的全部标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 为空时保持保存顺序
+    #[serde(default)]
+    pub sort: Option<TemplateSort>,
+}
+
+/// 模板导入导出格式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFormat {
+    Json,
+    Csv,
+}
+
+/// 导入时与已有模板冲突 (ID 或名称相同) 的处理方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflict {
+    /// 保留已有模板，跳过导入的模板
+    #[default]
+    Skip,
+    /// 用导入的内容覆盖已有模板 (生成新的修订版本)
+    Overwrite,
+    /// 以新 ID 另存一份
+    Duplicate,
+}
+
+/// 导入导出的模板 (不含使用记录和修订版本等本地信息)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateExport {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub content: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// 模板导入结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// 新增的模板数
+    pub created: u32,
+    /// 覆盖的模板数
+    pub overwritten: u32,
+    /// 以新 ID 另存的模板数
+    pub duplicated: u32,
+    /// 因冲突跳过的模板数
+    pub skipped: u32,
+    /// 校验未通过的模板 (名称: 原因)
+    pub errors: Vec<String>,
 }
 
 /// 评论任务
@@ -97,6 +394,18 @@ pub struct CommentTask {
     pub content: String,
     pub status: TaskStatus,
     pub error_msg: Option<String>,
+    /// B站返回的错误码
+    #[serde(default)]
+    pub error_code: Option<i32>,
+    /// 审核备注 (如划掉的原因)
+    #[serde(default)]
+    pub review_note: Option<String>,
+    /// content 是否已按视频渲染 (否则为原始模板)
+    #[serde(default)]
+    pub rendered: bool,
+    /// 发送成功后的评论 ID
+    #[serde(default)]
+    pub rpid: Option<u64>,
     pub created_at: i64,
     pub completed_at: Option<i64>,
 }
@@ -110,6 +419,31 @@ pub enum TaskStatus {
     Success,
     Failed,
     Cancelled,
+    /// 预检未通过，未发送
+    Skipped,
+    /// 演练模式下将会发送
+    WouldSend,
+    /// 审核时被划掉，不会发送
+    Struck,
+}
+
+/// 批次状态
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchState {
+    /// 草稿，等待审核
+    Draft,
+    /// 已审核通过，等待执行
+    Approved,
+    /// 已排期，等待开始时间
+    Scheduled,
+    /// 执行中
+    #[default]
+    Running,
+    /// 已执行完毕
+    Finished,
+    /// 已取消
+    Cancelled,
 }
 
 /// 评论结果
@@ -118,6 +452,16 @@ pub struct CommentResult {
     pub success: bool,
     pub rpid: Option<u64>,
     pub error_msg: Option<String>,
+    /// B站返回的错误码
+    #[serde(default)]
+    pub error_code: Option<i32>,
+}
+
+/// 评论互动数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplyEngagement {
+    pub likes: u64,
+    pub replies: u64,
 }
 
 /// 批量任务状态
@@ -128,5 +472,194 @@ pub struct BatchStatus {
     pub completed: usize,
     pub success: usize,
     pub failed: usize,
+    #[serde(default)]
+    pub skipped: usize,
+    /// 演练模式 (不实际发送评论)
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub state: BatchState,
+    /// 创建人 (审核流程中使用)
+    #[serde(default)]
+    pub created_by: Option<String>,
+    /// 审核人
+    #[serde(default)]
+    pub approved_by: Option<String>,
+    #[serde(default)]
+    pub approved_at: Option<i64>,
+    #[serde(default)]
+    pub created_at: i64,
+    /// 执行计划
+    #[serde(default)]
+    pub schedule: Option<BatchSchedule>,
+    /// 等待开始或等待进入允许时间段时，下一次执行的时间戳
+    #[serde(default)]
+    pub next_run_at: Option<i64>,
+    /// 评论模板原文
+    #[serde(default)]
+    pub template: String,
+    /// 严格模式: 未知占位符视为错误
+    #[serde(default)]
+    pub strict_template: bool,
+    /// 使用的模板 ID
+    #[serde(default)]
+    pub template_id: Option<String>,
+    /// 使用的模板修订版本
+    #[serde(default)]
+    pub template_revision: Option<u32>,
     pub tasks: Vec<CommentTask>,
 }
+
+/// 某个错误码的失败次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureCodeCount {
+    /// 错误码，为空表示网络错误等没有错误码的失败
+    pub code: Option<i32>,
+    pub message: Option<String>,
+    pub count: u32,
+}
+
+/// 模板评论的平均互动数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateEngagement {
+    /// 成功获取互动数据的评论数
+    pub sampled: u32,
+    pub avg_likes: f64,
+    pub avg_replies: f64,
+}
+
+/// 模板使用统计 (由批次历史汇总，不含演练批次)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateStats {
+    pub template_id: String,
+    /// 模板名称，模板已删除时为空
+    pub name: Option<String>,
+    /// 使用该模板执行的批次数
+    pub batches: u32,
+    pub success: u32,
+    pub failed: u32,
+    /// 成功率 (成功 / (成功 + 失败))，没有发送记录时为 0
+    pub success_rate: f64,
+    /// 按次数从多到少排列
+    pub failure_codes: Vec<FailureCodeCount>,
+    pub last_used_at: Option<i64>,
+    /// 未请求互动数据时为空
+    pub engagement: Option<TemplateEngagement>,
+}
+
+/// 已发送评论记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentLedgerEntry {
+    pub aid: u64,
+    pub bvid: String,
+    pub rpid: Option<u64>,
+    pub content: String,
+    /// 所属批次，单条发送时为空
+    pub batch_id: Option<String>,
+    pub template_id: Option<String>,
+    pub sent_at: i64,
+}
+
+/// 数据目录来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDirSource {
+    /// 命令行参数 `--data-dir`
+    CommandLine,
+    /// 环境变量 `BILI_COMMENT_DATA_DIR`
+    Environment,
+    /// 便携模式，数据保存在可执行文件旁
+    Portable,
+    /// 系统数据目录
+    Default,
+}
+
+/// 数据目录信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataLocation {
+    pub path: PathBuf,
+    pub source: DataDirSource,
+}
+
+/// 数据备份的导出或导入结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub templates: usize,
+    pub template_revisions: usize,
+    pub batches: usize,
+    pub ledger_entries: usize,
+    pub filter_presets: usize,
+    pub access_list_entries: usize,
+    pub sensitive_words: usize,
+    /// 是否包含 (或已恢复) 登录凭证
+    pub credential: bool,
+    /// 备份创建时间
+    pub created_at: i64,
+    /// 导入前自动保存的当前数据备份，用于撤销本次导入
+    pub safety_backup: Option<PathBuf>,
+}
+
+/// 存储文件加载问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageIssue {
+    /// 出问题的文件路径
+    pub file: String,
+    pub error: String,
+    /// 损坏文件被隔离后的路径 (隔离失败时为空)
+    pub quarantined_to: Option<String>,
+    /// 是否已从备份恢复
+    pub restored: bool,
+    pub occurred_at: i64,
+}
+
+/// 名单类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessListKind {
+    /// 屏蔽名单: 不允许评论
+    Blocklist,
+    /// 允许名单: 开启仅允许名单模式时只评论名单内的 UP主 或视频
+    Allowlist,
+}
+
+/// 名单条目的对象类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessListTarget {
+    /// UP主 (mid)
+    Author,
+    /// 视频 (aid 或 bvid)
+    Video,
+}
+
+/// 屏蔽/允许名单条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessListEntry {
+    pub kind: AccessListKind,
+    pub target: AccessListTarget,
+    /// UP主 mid，或视频的 aid (纯数字) / bvid
+    pub value: String,
+    /// 备注 (如对方要求不再评论)
+    pub reason: Option<String>,
+    pub created_at: i64,
+    /// 最近一次更新备注的时间
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// 敏感词类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WordKind {
+    /// 普通文本 (不区分大小写)
+    Plain,
+    /// 正则表达式
+    Regex,
+}
+
+/// 本地敏感词条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitiveWord {
+    pub pattern: String,
+    pub kind: WordKind,
+}
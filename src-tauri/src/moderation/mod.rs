@@ -0,0 +1,135 @@
+// 本地敏感词检查模块
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use regex::{Regex, RegexBuilder};
+use std::sync::Arc;
+
+use crate::models::{SensitiveWord, WordKind};
+use crate::storage::{db, sensitive_words};
+
+/// 编译后的敏感词匹配器
+pub struct WordMatcher {
+    plain: Vec<String>,
+    regex: Vec<(String, Regex)>,
+}
+
+impl WordMatcher {
+    /// 编译敏感词列表，正则表达式无效时返回错误
+    pub fn compile(words: &[SensitiveWord]) -> Result<Self, String> {
+        let mut plain = Vec::new();
+        let mut regex = Vec::new();
+
+        for word in words {
+            if word.pattern.is_empty() {
+                continue;
+            }
+            match word.kind {
+                WordKind::Plain => plain.push(word.pattern.to_lowercase()),
+                WordKind::Regex => {
+                    let re = RegexBuilder::new(&word.pattern)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| format!("无效的正则表达式 \"{}\": {}", word.pattern, e))?;
+                    regex.push((word.pattern.clone(), re));
+                }
+            }
+        }
+
+        Ok(Self { plain, regex })
+    }
+
+    /// 返回内容中命中的敏感词 (正则条目返回匹配到的文本)
+    pub fn find_matches(&self, content: &str) -> Vec<String> {
+        let lowered = content.to_lowercase();
+        let mut matches: Vec<String> = self
+            .plain
+            .iter()
+            .filter(|word| lowered.contains(word.as_str()))
+            .cloned()
+            .collect();
+
+        for (_, re) in &self.regex {
+            if let Some(m) = re.find(content) {
+                matches.push(m.as_str().to_string());
+            }
+        }
+
+        matches
+    }
+}
+
+/// 当前生效的匹配器
+static MATCHER: Lazy<RwLock<Arc<WordMatcher>>> = Lazy::new(|| RwLock::new(Arc::new(load())));
+
+/// 从存储加载并编译敏感词 (存在无效条目时跳过正则部分并记录日志)
+fn load() -> WordMatcher {
+    let words = sensitive_words::load_sensitive_words().unwrap_or_else(|e| {
+        db::report_issue("sensitive_words", &e);
+        Vec::new()
+    });
+    WordMatcher::compile(&words).unwrap_or_else(|e| {
+        log::error!("敏感词列表编译失败: {}", e);
+        let plain: Vec<SensitiveWord> = words
+            .into_iter()
+            .filter(|w| w.kind == WordKind::Plain)
+            .collect();
+        WordMatcher::compile(&plain).unwrap_or(WordMatcher {
+            plain: Vec::new(),
+            regex: Vec::new(),
+        })
+    })
+}
+
+/// 替换当前生效的匹配器
+pub fn set_matcher(matcher: WordMatcher) {
+    *MATCHER.write() = Arc::new(matcher);
+}
+
+/// 检查内容，返回命中的敏感词
+pub fn check(content: &str) -> Vec<String> {
+    let matcher = MATCHER.read().clone();
+    matcher.find_matches(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(pattern: &str, kind: WordKind) -> SensitiveWord {
+        SensitiveWord {
+            pattern: pattern.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn plain_words_ignore_case() {
+        let matcher = WordMatcher::compile(&[word("Spam", WordKind::Plain)]).unwrap();
+        assert_eq!(matcher.find_matches("this is SPAM"), ["spam"]);
+        assert!(matcher.find_matches("nothing here").is_empty());
+    }
+
+    #[test]
+    fn regex_words_return_matched_text() {
+        let matcher = WordMatcher::compile(&[
+            word(r"加\s*微信", WordKind::Regex),
+            word(r"v\d+", WordKind::Regex),
+        ])
+        .unwrap();
+        assert_eq!(matcher.find_matches("请加 微信 V123"), ["加 微信", "V123"]);
+    }
+
+    #[test]
+    fn empty_patterns_are_ignored() {
+        let matcher = WordMatcher::compile(&[word("", WordKind::Plain)]).unwrap();
+        assert!(matcher.find_matches("anything").is_empty());
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        let result =
+            WordMatcher::compile(&[word("ok", WordKind::Plain), word("(", WordKind::Regex)]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,7 @@
+// 评论模板渲染模块
+
+pub mod template;
+pub mod validate;
+
+pub use template::*;
+pub use validate::*;
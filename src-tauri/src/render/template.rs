@@ -0,0 +1,331 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Local, TimeZone};
+
+use crate::i18n::{self, Msg};
+use crate::models::{UserInfo, VideoItem};
+
+/// 发布日期的默认格式
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// 视频相关占位符
+pub const VIDEO_PLACEHOLDERS: &[&str] = &["title", "author", "bvid", "aid", "pubdate", "duration"];
+
+/// 登录用户相关占位符
+pub const USER_PLACEHOLDERS: &[&str] = &["uname", "uid"];
+
+/// 渲染上下文
+pub struct RenderContext<'a> {
+    pub video: &'a VideoItem,
+    pub user: Option<&'a UserInfo>,
+}
+
+/// 模板渲染错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    /// 未知的占位符
+    UnknownPlaceholder(String),
+    /// 存在未闭合的 `{`
+    UnclosedBrace,
+    /// 占位符需要登录用户信息
+    MissingUser(String),
+    /// 占位符的格式参数无效
+    InvalidFormat(String, String),
+}
+
+impl RenderError {
+    /// 转换为用户友好的错误信息 (按当前语言)
+    pub fn to_user_message(&self) -> String {
+        match self {
+            Self::UnknownPlaceholder(name) => i18n::tf(Msg::TemplateUnknownPlaceholder, &[name]),
+            Self::UnclosedBrace => i18n::t(Msg::TemplateUnclosedBrace).to_string(),
+            Self::MissingUser(name) => i18n::tf(Msg::TemplateMissingUser, &[name]),
+            Self::InvalidFormat(name, format) => {
+                i18n::tf(Msg::TemplateInvalidFormat, &[name, format])
+            }
+        }
+    }
+}
+
+/// 模板片段
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder { name: &'a str, format: Option<&'a str> },
+}
+
+/// 解析模板: `{name}` 或 `{name:format}` 为占位符，`{{` 和 `}}` 为字面量花括号
+///
+/// 非严格模式下未闭合的 `{` 按字面量处理
+fn parse(template: &str, strict: bool) -> Result<Vec<Segment<'_>>, RenderError> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        if pos > 0 {
+            segments.push(Segment::Text(&rest[..pos]));
+        }
+        let tail = &rest[pos..];
+
+        if let Some(after) = tail.strip_prefix("{{") {
+            segments.push(Segment::Text("{"));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            segments.push(Segment::Text("}"));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix('}') {
+            // 单独的 `}` 按字面量处理
+            segments.push(Segment::Text("}"));
+            rest = after;
+        } else {
+            let Some(end) = tail.find('}') else {
+                if strict {
+                    return Err(RenderError::UnclosedBrace);
+                }
+                segments.push(Segment::Text(tail));
+                rest = "";
+                break;
+            };
+            let body = &tail[1..end];
+            let (name, format) = match body.split_once(':') {
+                Some((name, format)) => (name.trim(), Some(format)),
+                None => (body.trim(), None),
+            };
+            segments.push(Segment::Placeholder { name, format });
+            rest = &tail[end + 1..];
+        }
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+
+    Ok(segments)
+}
+
+/// 列出模板中使用的占位符名称 (存在未闭合的 `{` 时返回错误)
+pub fn placeholders(template: &str) -> Result<Vec<String>, RenderError> {
+    Ok(parse(template, true)?
+        .into_iter()
+        .filter_map(|seg| match seg {
+            Segment::Placeholder { name, .. } => Some(name.to_string()),
+            Segment::Text(_) => None,
+        })
+        .collect())
+}
+
+/// 是否为已知占位符
+pub fn is_known_placeholder(name: &str) -> bool {
+    VIDEO_PLACEHOLDERS.contains(&name) || USER_PLACEHOLDERS.contains(&name)
+}
+
+/// 模板是否使用了登录用户相关的占位符
+pub fn needs_user(template: &str) -> bool {
+    parse(template, false).is_ok_and(|segments| {
+        segments.iter().any(|seg| {
+            matches!(seg, Segment::Placeholder { name, .. } if USER_PLACEHOLDERS.contains(name))
+        })
+    })
+}
+
+/// 格式化发布时间
+fn format_pubdate(timestamp: i64, format: &str) -> Result<String, ()> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return Err(());
+    }
+
+    let time = Local.timestamp_opt(timestamp, 0).single().ok_or(())?;
+    Ok(time.format_with_items(items.into_iter()).to_string())
+}
+
+/// 解析单个占位符的值，未知占位符返回 None
+fn resolve(
+    name: &str,
+    format: Option<&str>,
+    ctx: &RenderContext,
+) -> Option<Result<String, RenderError>> {
+    let video = ctx.video;
+    let value = match name {
+        "title" => Ok(video.title.clone()),
+        "author" => Ok(video.author.clone()),
+        "bvid" => Ok(video.bvid.clone()),
+        "aid" => Ok(video.aid.to_string()),
+        "duration" => Ok(video.duration.clone()),
+        "pubdate" => {
+            let format = format.unwrap_or(DEFAULT_DATE_FORMAT);
+            format_pubdate(video.pubdate, format)
+                .map_err(|_| RenderError::InvalidFormat(name.to_string(), format.to_string()))
+        }
+        "uname" | "uid" => match ctx.user {
+            Some(user) if name == "uname" => Ok(user.uname.clone()),
+            Some(user) => Ok(user.mid.to_string()),
+            None => Err(RenderError::MissingUser(name.to_string())),
+        },
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// 渲染模板
+///
+/// 严格模式下未知占位符、缺少用户信息等问题会返回错误；
+/// 非严格模式下这些占位符保持原样输出
+pub fn render(template: &str, ctx: &RenderContext, strict: bool) -> Result<String, RenderError> {
+    let mut out = String::with_capacity(template.len());
+
+    for segment in parse(template, strict)? {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Placeholder { name, format } => match resolve(name, format, ctx) {
+                Some(Ok(value)) => out.push_str(&value),
+                Some(Err(e)) if strict => return Err(e),
+                None if strict => return Err(RenderError::UnknownPlaceholder(name.to_string())),
+                _ => {
+                    out.push('{');
+                    out.push_str(name);
+                    if let Some(format) = format {
+                        out.push(':');
+                        out.push_str(format);
+                    }
+                    out.push('}');
+                }
+            },
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-06-15 12:00:00 UTC
+    const PUBDATE: i64 = 1_718_452_800;
+
+    fn video() -> VideoItem {
+        VideoItem {
+            aid: 170001,
+            bvid: "BV17x411w7KC".to_string(),
+            title: "标题".to_string(),
+            author: "UP主".to_string(),
+            mid: 1,
+            pic: String::new(),
+            play: 0,
+            danmaku: 0,
+            pubdate: PUBDATE,
+            duration: "3:25".to_string(),
+            description: String::new(),
+            title_highlights: Vec::new(),
+        }
+    }
+
+    fn user() -> UserInfo {
+        UserInfo {
+            mid: 42,
+            uname: "用户".to_string(),
+            face: String::new(),
+            is_login: true,
+        }
+    }
+
+    fn render_with(
+        template: &str,
+        user: Option<&UserInfo>,
+        strict: bool,
+    ) -> Result<String, RenderError> {
+        let video = video();
+        let ctx = RenderContext {
+            video: &video,
+            user,
+        };
+        render(template, &ctx, strict)
+    }
+
+    #[test]
+    fn renders_video_placeholders() {
+        let out = render_with(
+            "{title} by {author} ({bvid}/av{aid}, {duration})",
+            None,
+            true,
+        );
+        assert_eq!(out.unwrap(), "标题 by UP主 (BV17x411w7KC/av170001, 3:25)");
+    }
+
+    #[test]
+    fn double_braces_are_literal() {
+        assert_eq!(render_with("{{title}}", None, true).unwrap(), "{title}");
+        assert_eq!(render_with("{{{title}}}", None, true).unwrap(), "{标题}");
+        assert_eq!(render_with("a }} b", None, true).unwrap(), "a } b");
+    }
+
+    #[test]
+    fn stray_closing_brace_is_literal() {
+        assert_eq!(render_with("a } b", None, true).unwrap(), "a } b");
+    }
+
+    #[test]
+    fn unclosed_brace() {
+        assert_eq!(
+            render_with("hi {title", None, true),
+            Err(RenderError::UnclosedBrace)
+        );
+        assert_eq!(render_with("hi {title", None, false).unwrap(), "hi {title");
+        assert!(placeholders("{title").is_err());
+    }
+
+    #[test]
+    fn unknown_placeholder() {
+        assert_eq!(
+            render_with("{nope}", None, true),
+            Err(RenderError::UnknownPlaceholder("nope".to_string()))
+        );
+        assert_eq!(render_with("{nope:x}", None, false).unwrap(), "{nope:x}");
+    }
+
+    #[test]
+    fn user_placeholders_need_user() {
+        assert_eq!(
+            render_with("{uname}", None, true),
+            Err(RenderError::MissingUser("uname".to_string()))
+        );
+        assert_eq!(render_with("{uname}", None, false).unwrap(), "{uname}");
+        assert_eq!(
+            render_with("{uname}#{uid}", Some(&user()), true).unwrap(),
+            "用户#42"
+        );
+        assert!(needs_user("hi {uid}"));
+        assert!(!needs_user("hi {{uid}}"));
+    }
+
+    #[test]
+    fn pubdate_formats() {
+        let local = Local.timestamp_opt(PUBDATE, 0).unwrap();
+        assert_eq!(
+            render_with("{pubdate}", None, true).unwrap(),
+            local.format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(
+            render_with("{pubdate:%Y年%m月}", None, true).unwrap(),
+            local.format("%Y年%m月").to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_pubdate_format() {
+        assert_eq!(
+            render_with("{pubdate:%Q}", None, true),
+            Err(RenderError::InvalidFormat(
+                "pubdate".to_string(),
+                "%Q".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn lists_placeholders() {
+        assert_eq!(
+            placeholders("{title} {{x}} { author } {pubdate:%Y}").unwrap(),
+            vec!["title", "author", "pubdate"]
+        );
+    }
+}
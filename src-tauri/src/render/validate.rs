@@ -0,0 +1,201 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use super::template::{self, RenderContext, RenderError};
+use crate::i18n::{self, Msg};
+use crate::models::{UserInfo, VideoItem};
+use crate::moderation;
+
+/// B站评论的最大字数
+pub const MAX_COMMENT_CHARS: usize = 1000;
+
+/// 模板问题类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateIssueKind {
+    TooLong,
+    Empty,
+    UnknownPlaceholder,
+    UnclosedBrace,
+    InvalidFormat,
+    MissingUser,
+    BannedWord,
+    UnsupportedChar,
+}
+
+/// 模板问题
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateIssue {
+    pub kind: TemplateIssueKind,
+    pub message: String,
+}
+
+/// 模板预览结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplatePreview {
+    /// 渲染结果 (模板无法解析时为空)
+    pub rendered: Option<String>,
+    /// 渲染结果的字数
+    pub char_count: usize,
+    pub issues: Vec<TemplateIssue>,
+}
+
+impl TemplatePreview {
+    /// 是否没有任何问题
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// 将所有问题合并为一条错误信息
+    pub fn issues_message(&self) -> String {
+        self.issues
+            .iter()
+            .map(|i| i.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+impl From<RenderError> for TemplateIssue {
+    fn from(err: RenderError) -> Self {
+        let kind = match &err {
+            RenderError::UnknownPlaceholder(_) => TemplateIssueKind::UnknownPlaceholder,
+            RenderError::UnclosedBrace => TemplateIssueKind::UnclosedBrace,
+            RenderError::MissingUser(_) => TemplateIssueKind::MissingUser,
+            RenderError::InvalidFormat(..) => TemplateIssueKind::InvalidFormat,
+        };
+        Self {
+            kind,
+            message: err.to_user_message(),
+        }
+    }
+}
+
+/// 用于校验模板的示例视频
+pub fn sample_video() -> VideoItem {
+    VideoItem {
+        aid: 170001,
+        bvid: "BV17x411w7KC".to_string(),
+        title: "示例视频标题".to_string(),
+        author: "示例UP主".to_string(),
+        mid: 1,
+        pic: String::new(),
+        play: 0,
+        danmaku: 0,
+        pubdate: chrono::Utc::now().timestamp(),
+        duration: "10:00".to_string(),
+        description: String::new(),
+        title_highlights: Vec::new(),
+    }
+}
+
+/// 用于校验模板的示例用户
+pub fn sample_user() -> UserInfo {
+    UserInfo {
+        mid: 1,
+        uname: "示例用户".to_string(),
+        face: String::new(),
+        is_login: true,
+    }
+}
+
+/// 是否为B站评论不支持的字符 (控制字符、私用区字符、替换字符)
+fn is_unsupported_char(c: char) -> bool {
+    (c.is_control() && c != '\n') || ('\u{E000}'..='\u{F8FF}').contains(&c) || c == '\u{FFFD}'
+}
+
+/// 内容中不支持的字符 (去重，按码位排序)
+fn unsupported_chars(content: &str) -> BTreeSet<char> {
+    content.chars().filter(|c| is_unsupported_char(*c)).collect()
+}
+
+/// 检查渲染后的评论内容
+pub fn check_content(content: &str, issues: &mut Vec<TemplateIssue>) {
+    let char_count = content.chars().count();
+
+    if content.trim().is_empty() {
+        issues.push(TemplateIssue {
+            kind: TemplateIssueKind::Empty,
+            message: i18n::t(Msg::IssueEmpty).to_string(),
+        });
+    }
+
+    if char_count > MAX_COMMENT_CHARS {
+        issues.push(TemplateIssue {
+            kind: TemplateIssueKind::TooLong,
+            message: i18n::tf(Msg::IssueTooLong, &[&char_count, &MAX_COMMENT_CHARS]),
+        });
+    }
+
+    for word in moderation::check(content) {
+        issues.push(TemplateIssue {
+            kind: TemplateIssueKind::BannedWord,
+            message: i18n::tf(Msg::IssueBannedWord, &[&word]),
+        });
+    }
+
+    for c in unsupported_chars(content) {
+        issues.push(TemplateIssue {
+            kind: TemplateIssueKind::UnsupportedChar,
+            message: i18n::tf(Msg::IssueUnsupportedChar, &[&format!("U+{:04X}", c as u32)]),
+        });
+    }
+}
+
+/// 渲染模板并报告所有问题
+pub fn preview(template_text: &str, ctx: &RenderContext) -> TemplatePreview {
+    let mut issues: Vec<TemplateIssue> = Vec::new();
+
+    // 语法与占位符检查
+    match template::placeholders(template_text) {
+        Ok(names) => {
+            for name in names {
+                if !template::is_known_placeholder(&name) {
+                    issues.push(RenderError::UnknownPlaceholder(name).into());
+                }
+            }
+        }
+        Err(e) => issues.push(e.into()),
+    }
+
+    // 严格渲染以发现缺少用户信息、格式无效等问题，再宽松渲染得到预览文本
+    if let Err(e) = template::render(template_text, ctx, true) {
+        if !matches!(
+            e,
+            RenderError::UnknownPlaceholder(_) | RenderError::UnclosedBrace
+        ) {
+            issues.push(e.into());
+        }
+    }
+    let rendered = template::render(template_text, ctx, false).ok();
+
+    if let Some(content) = &rendered {
+        check_content(content, &mut issues);
+    }
+
+    TemplatePreview {
+        char_count: rendered.as_ref().map_or(0, |r| r.chars().count()),
+        rendered,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_chars_are_reported_once() {
+        let found = unsupported_chars("a\u{FFFD}b\u{0007}c\u{FFFD}\u{0007}\u{E000}");
+        assert_eq!(
+            found.into_iter().collect::<Vec<_>>(),
+            vec!['\u{0007}', '\u{E000}', '\u{FFFD}']
+        );
+    }
+
+    #[test]
+    fn newline_is_supported() {
+        assert!(unsupported_chars("第一行\n第二行").is_empty());
+    }
+}
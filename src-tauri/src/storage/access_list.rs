@@ -0,0 +1,167 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::models::{AccessListEntry, AccessListKind, AccessListTarget, VideoItem};
+
+use super::db::{self, from_text, to_text};
+
+const SELECT_ENTRY: &str =
+    "SELECT kind, target, value, reason, created_at, updated_at FROM access_list";
+
+/// 从查询结果行构造名单条目
+fn from_row(row: &Row) -> rusqlite::Result<AccessListEntry> {
+    Ok(AccessListEntry {
+        kind: from_text(row.get(0)?)?,
+        target: from_text(row.get(1)?)?,
+        value: row.get(2)?,
+        reason: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+/// 写入名单条目 (已存在时只更新备注和更新时间，保留最初的添加时间)
+pub(super) fn upsert(conn: &Connection, entry: &AccessListEntry) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO access_list (kind, target, value, reason, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (kind, target, value) DO UPDATE SET
+             reason = excluded.reason,
+             updated_at = excluded.updated_at",
+        params![
+            to_text(&entry.kind),
+            to_text(&entry.target),
+            entry.value,
+            entry.reason,
+            entry.created_at,
+            // 旧版备份中的条目没有更新时间
+            entry.updated_at.max(entry.created_at),
+        ],
+    )?;
+    Ok(())
+}
+
+/// 加载名单条目 (可按名单类型筛选，最新的在前)
+pub fn load_entries(kind: Option<AccessListKind>) -> Result<Vec<AccessListEntry>, String> {
+    db::with_conn(|conn| match kind {
+        Some(kind) => {
+            let mut stmt = conn.prepare(&format!(
+                "{} WHERE kind = ?1 ORDER BY created_at DESC",
+                SELECT_ENTRY
+            ))?;
+            let rows = stmt.query_map([to_text(&kind)], from_row)?;
+            rows.collect()
+        }
+        None => {
+            let mut stmt = conn.prepare(&format!("{} ORDER BY created_at DESC", SELECT_ENTRY))?;
+            let rows = stmt.query_map([], from_row)?;
+            rows.collect()
+        }
+    })
+}
+
+/// 保存名单条目，返回保存后的条目 (已存在时 `created_at` 为最初的添加时间)
+pub fn save_entry(entry: &AccessListEntry) -> Result<AccessListEntry, String> {
+    db::with_conn(|conn| {
+        upsert(conn, entry)?;
+        conn.query_row(
+            &format!(
+                "{} WHERE kind = ?1 AND target = ?2 AND value = ?3",
+                SELECT_ENTRY
+            ),
+            params![to_text(&entry.kind), to_text(&entry.target), entry.value],
+            from_row,
+        )
+    })
+}
+
+/// 删除名单条目，返回是否存在
+pub fn remove_entry(
+    kind: AccessListKind,
+    target: AccessListTarget,
+    value: &str,
+) -> Result<bool, String> {
+    db::with_conn(|conn| {
+        let count = conn.execute(
+            "DELETE FROM access_list WHERE kind = ?1 AND target = ?2 AND value = ?3",
+            params![to_text(&kind), to_text(&target), value],
+        )?;
+        Ok(count > 0)
+    })
+}
+
+/// 查找与视频 (UP主 mid、aid 或 bvid) 匹配的名单条目
+pub fn find_matches(
+    kind: AccessListKind,
+    video: &VideoItem,
+) -> Result<Vec<AccessListEntry>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare_cached(&format!(
+            "{} WHERE kind = ?1 AND ((target = ?2 AND value = ?3)
+                 OR (target = ?4 AND value IN (?5, ?6)))",
+            SELECT_ENTRY
+        ))?;
+        let rows = stmt.query_map(
+            params![
+                to_text(&kind),
+                to_text(&AccessListTarget::Author),
+                video.mid.to_string(),
+                to_text(&AccessListTarget::Video),
+                video.aid.to_string(),
+                video.bvid,
+            ],
+            from_row,
+        )?;
+        rows.collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 内存数据库，只建名单表 (v3 及之后的迁移)
+    fn open() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        for sql in &db::MIGRATIONS[2..] {
+            conn.execute_batch(sql).unwrap();
+        }
+        conn
+    }
+
+    fn entry(reason: &str, created_at: i64, updated_at: i64) -> AccessListEntry {
+        AccessListEntry {
+            kind: AccessListKind::Blocklist,
+            target: AccessListTarget::Author,
+            value: "42".to_string(),
+            reason: Some(reason.to_string()),
+            created_at,
+            updated_at,
+        }
+    }
+
+    fn load(conn: &Connection) -> Vec<AccessListEntry> {
+        let mut stmt = conn.prepare(SELECT_ENTRY).unwrap();
+        let rows = stmt.query_map([], from_row).unwrap();
+        rows.collect::<rusqlite::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn upsert_keeps_created_at() {
+        let conn = open();
+        upsert(&conn, &entry("first", 100, 100)).unwrap();
+        upsert(&conn, &entry("second", 200, 200)).unwrap();
+
+        let entries = load(&conn);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].reason.as_deref(), Some("second"));
+        assert_eq!(entries[0].created_at, 100);
+        assert_eq!(entries[0].updated_at, 200);
+    }
+
+    #[test]
+    fn upsert_fills_missing_updated_at() {
+        let conn = open();
+        upsert(&conn, &entry("old backup", 100, 0)).unwrap();
+        assert_eq!(load(&conn)[0].updated_at, 100);
+    }
+}
@@ -0,0 +1,299 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Msg};
+use crate::models::{
+    AccessListEntry, BackupSummary, BatchState, BatchStatus, CommentLedgerEntry, CommentTemplate,
+    FilterPreset, LoginCredential, SensitiveWord, Settings, TaskStatus, TemplateRevision,
+};
+use crate::moderation::{self, WordMatcher};
+
+use super::{access_list, batch, credential, db, filter_preset, get_app_data_dir, ledger};
+use super::{sensitive_words, settings, template, template_revision, write_atomic};
+
+/// 备份文件格式版本，结构不兼容时递增
+const BACKUP_VERSION: u32 = 1;
+
+/// 导入前自动备份所在的目录名 (位于数据目录下)
+const SAFETY_BACKUP_DIR: &str = "backups";
+
+/// 加密后的数据 (均为 Base64)
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedBlob {
+    /// 密钥派生 (Argon2id) 使用的盐
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 备份文件内容
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupArchive {
+    version: u32,
+    created_at: i64,
+    templates: Vec<CommentTemplate>,
+    template_revisions: Vec<TemplateRevision>,
+    batches: Vec<BatchStatus>,
+    ledger: Vec<CommentLedgerEntry>,
+    settings: Settings,
+    #[serde(default)]
+    filter_presets: Vec<FilterPreset>,
+    #[serde(default)]
+    access_list: Vec<AccessListEntry>,
+    #[serde(default)]
+    sensitive_words: Vec<SensitiveWord>,
+    /// 登录凭证，仅在导出时明确要求才包含，使用密码加密
+    #[serde(default)]
+    credential: Option<EncryptedBlob>,
+}
+
+impl BackupArchive {
+    fn summary(&self, credential: bool) -> BackupSummary {
+        BackupSummary {
+            templates: self.templates.len(),
+            template_revisions: self.template_revisions.len(),
+            batches: self.batches.len(),
+            ledger_entries: self.ledger.len(),
+            filter_presets: self.filter_presets.len(),
+            access_list_entries: self.access_list.len(),
+            sensitive_words: self.sensitive_words.len(),
+            credential,
+            created_at: self.created_at,
+            safety_backup: None,
+        }
+    }
+}
+
+/// 从密码派生 AES-256 密钥
+fn derive_cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm, String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("密钥派生失败: {}", e))?;
+    Aes256Gcm::new_from_slice(&key).map_err(|e| format!("密钥无效: {}", e))
+}
+
+/// 使用密码加密登录凭证
+fn encrypt_credential(
+    credential: &LoginCredential,
+    password: &str,
+) -> Result<EncryptedBlob, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = derive_cipher(password, &salt)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let plaintext = serde_json::to_vec(credential).map_err(|e| format!("序列化失败: {}", e))?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|e| format!("加密失败: {}", e))?;
+
+    Ok(EncryptedBlob {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+/// 使用密码解密登录凭证
+fn decrypt_credential(blob: &EncryptedBlob, password: &str) -> Result<LoginCredential, String> {
+    let decode = |value: &str| {
+        STANDARD
+            .decode(value)
+            .map_err(|e| i18n::tf(Msg::BackupInvalidFile, &[&e]))
+    };
+    let salt = decode(&blob.salt)?;
+    let nonce = decode(&blob.nonce)?;
+    let ciphertext = decode(&blob.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(i18n::tf(Msg::BackupInvalidFile, &[&"nonce"]));
+    }
+
+    let cipher = derive_cipher(password, &salt)?;
+    // 认证失败即密码错误 (或数据被篡改)
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| i18n::t(Msg::BackupWrongPassword).to_string())?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| i18n::tf(Msg::BackupInvalidFile, &[&e]))
+}
+
+/// 读取当前所有数据 (调用方需持有存储锁)
+fn snapshot(credential: Option<EncryptedBlob>) -> Result<BackupArchive, String> {
+    Ok(BackupArchive {
+        version: BACKUP_VERSION,
+        created_at: chrono::Utc::now().timestamp(),
+        templates: template::load_templates()?,
+        template_revisions: template_revision::load_all_revisions()?,
+        batches: batch::load_batches()?,
+        ledger: ledger::load_entries()?,
+        settings: settings::current(),
+        filter_presets: filter_preset::load_presets()?,
+        access_list: access_list::load_entries(None)?,
+        sensitive_words: sensitive_words::load_sensitive_words()?,
+        credential,
+    })
+}
+
+/// 写入备份文件
+fn write_archive(path: &Path, archive: &BackupArchive) -> Result<(), String> {
+    let content =
+        serde_json::to_string_pretty(archive).map_err(|e| format!("序列化失败: {}", e))?;
+    write_atomic(path, &content)
+}
+
+/// 读取并校验备份文件
+fn read_archive(path: &Path) -> Result<BackupArchive, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取备份文件失败: {}", e))?;
+
+    // 先检查版本，避免新版本格式的解析错误掩盖真正的原因
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| i18n::tf(Msg::BackupInvalidFile, &[&e]))?;
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version > BACKUP_VERSION as u64 {
+        return Err(i18n::tf(
+            Msg::BackupUnsupportedVersion,
+            &[&version, &BACKUP_VERSION],
+        ));
+    }
+
+    serde_json::from_value(value).map_err(|e| i18n::tf(Msg::BackupInvalidFile, &[&e]))
+}
+
+/// 导出所有数据到备份文件
+///
+/// 传入 `credential_password` 时同时导出登录凭证 (使用该密码加密)
+pub fn export_backup(
+    path: &Path,
+    credential_password: Option<&str>,
+) -> Result<BackupSummary, String> {
+    let _guard = super::lock();
+
+    let credential = match credential_password {
+        Some(password) => credential::load_credential()?
+            .map(|c| encrypt_credential(&c, password))
+            .transpose()?,
+        None => None,
+    };
+    let has_credential = credential.is_some();
+
+    let archive = snapshot(credential)?;
+    write_archive(path, &archive)?;
+
+    log::info!("数据已备份到 {:?}", path);
+    Ok(archive.summary(has_credential))
+}
+
+/// 导入前保存当前数据 (不含登录凭证)，返回备份文件路径
+fn save_safety_backup() -> Result<PathBuf, String> {
+    let name = format!(
+        "pre-restore-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = get_app_data_dir().join(SAFETY_BACKUP_DIR).join(name);
+    write_archive(&path, &snapshot(None)?)?;
+    Ok(path)
+}
+
+/// 备份中执行中或已排期的批次无法在本机继续，恢复为已取消
+fn cancel_in_flight(batch: &mut BatchStatus, now: i64) {
+    if !matches!(batch.state, BatchState::Running | BatchState::Scheduled) {
+        return;
+    }
+    for task in batch.tasks.iter_mut() {
+        if matches!(task.status, TaskStatus::Pending | TaskStatus::Running) {
+            task.status = TaskStatus::Cancelled;
+            task.completed_at = Some(now);
+        }
+    }
+    batch.state = BatchState::Cancelled;
+    batch.next_run_at = None;
+}
+
+/// 从备份文件恢复数据，替换当前的模板、批次、评论记录、设置、筛选预设、屏蔽/允许名单和敏感词
+///
+/// 备份包含登录凭证且提供了密码时一并恢复，否则保留当前登录状态。
+/// 恢复前会自动备份当前数据，恢复在同一事务中完成，失败时不修改任何数据。
+/// 返回恢复的登录凭证 (如有)，供调用方更新登录状态
+pub fn import_backup(
+    path: &Path,
+    credential_password: Option<&str>,
+) -> Result<(BackupSummary, Option<LoginCredential>), String> {
+    let mut archive = read_archive(path)?;
+    // 备份文件可能被手动修改，设置和敏感词需与保存时一样校验
+    archive.settings.validate()?;
+    let matcher = WordMatcher::compile(&archive.sensitive_words)?;
+
+    // 先解密，密码错误时不做任何修改
+    let restored_credential = match (&archive.credential, credential_password) {
+        (Some(blob), Some(password)) => Some(decrypt_credential(blob, password)?),
+        _ => None,
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    for batch in archive.batches.iter_mut() {
+        cancel_in_flight(batch, now);
+    }
+
+    let _guard = super::lock();
+    let safety_backup = save_safety_backup()?;
+
+    db::with_conn(|conn| {
+        let tx = conn.transaction()?;
+        tx.execute_batch(
+            "DELETE FROM tasks;
+             DELETE FROM batches;
+             DELETE FROM template_revisions;
+             DELETE FROM templates;
+             DELETE FROM comment_ledger;
+             DELETE FROM settings;
+             DELETE FROM filter_presets;
+             DELETE FROM access_list;",
+        )?;
+
+        for t in &archive.templates {
+            template::upsert(&tx, t)?;
+        }
+        for r in &archive.template_revisions {
+            template_revision::insert(&tx, r)?;
+        }
+        for b in &archive.batches {
+            batch::upsert(&tx, b)?;
+        }
+        for entry in &archive.ledger {
+            ledger::insert(&tx, entry)?;
+        }
+        settings::write(&tx, &archive.settings)?;
+        for p in &archive.filter_presets {
+            filter_preset::upsert(&tx, p)?;
+        }
+        for entry in &archive.access_list {
+            access_list::upsert(&tx, entry)?;
+        }
+        sensitive_words::replace(&tx, &archive.sensitive_words)?;
+        if let Some(c) = &restored_credential {
+            credential::write(&tx, c)?;
+        }
+
+        tx.commit()
+    })?;
+    settings::set_cached(&archive.settings);
+    moderation::set_matcher(matcher);
+
+    log::info!(
+        "已从 {:?} 恢复数据，导入前的数据已备份到 {:?}",
+        path,
+        safety_backup
+    );
+
+    let mut summary = archive.summary(restored_credential.is_some());
+    summary.safety_backup = Some(safety_backup);
+    Ok((summary, restored_credential))
+}
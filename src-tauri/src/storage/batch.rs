@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection, Row};
+
+use crate::models::{BatchStatus, CommentTask};
+
+use super::db::{self, from_json, from_text, to_json, to_text};
+
+/// 从查询结果行构造批次 (不含任务)
+fn batch_from_row(row: &Row) -> rusqlite::Result<BatchStatus> {
+    let schedule: Option<String> = row.get(12)?;
+    Ok(BatchStatus {
+        batch_id: row.get(0)?,
+        state: from_text(row.get(1)?)?,
+        dry_run: row.get(2)?,
+        total: row.get(3)?,
+        completed: row.get(4)?,
+        success: row.get(5)?,
+        failed: row.get(6)?,
+        skipped: row.get(7)?,
+        created_by: row.get(8)?,
+        approved_by: row.get(9)?,
+        approved_at: row.get(10)?,
+        created_at: row.get(11)?,
+        schedule: schedule.as_deref().map(from_json).transpose()?,
+        next_run_at: row.get(13)?,
+        template: row.get(14)?,
+        strict_template: row.get(15)?,
+        template_id: row.get(16)?,
+        template_revision: row.get(17)?,
+        tasks: Vec::new(),
+    })
+}
+
+/// 从查询结果行构造任务，返回所属批次 ID
+fn task_from_row(row: &Row) -> rusqlite::Result<(String, CommentTask)> {
+    let video: String = row.get(2)?;
+    let task = CommentTask {
+        id: row.get(0)?,
+        video: from_json(&video)?,
+        content: row.get(3)?,
+        status: from_text(row.get(4)?)?,
+        error_msg: row.get(5)?,
+        error_code: row.get(6)?,
+        review_note: row.get(7)?,
+        rendered: row.get(8)?,
+        rpid: row.get(9)?,
+        created_at: row.get(10)?,
+        completed_at: row.get(11)?,
+    };
+    Ok((row.get(1)?, task))
+}
+
+/// 写入批次及其任务 (已存在时覆盖)
+pub(super) fn upsert(conn: &Connection, batch: &BatchStatus) -> rusqlite::Result<()> {
+    write_batch(conn, batch)?;
+    for position in 0..batch.tasks.len() {
+        write_task(conn, batch, position)?;
+    }
+    Ok(())
+}
+
+/// 写入批次本身 (状态、统计等，不含任务)
+fn write_batch(conn: &Connection, batch: &BatchStatus) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO batches (batch_id, state, dry_run, total, completed, success, failed, \
+             skipped, created_by, approved_by, approved_at, created_at, schedule, next_run_at, \
+             template, strict_template, template_id, template_revision)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
+         ON CONFLICT (batch_id) DO UPDATE SET
+             state = excluded.state,
+             dry_run = excluded.dry_run,
+             total = excluded.total,
+             completed = excluded.completed,
+             success = excluded.success,
+             failed = excluded.failed,
+             skipped = excluded.skipped,
+             created_by = excluded.created_by,
+             approved_by = excluded.approved_by,
+             approved_at = excluded.approved_at,
+             created_at = excluded.created_at,
+             schedule = excluded.schedule,
+             next_run_at = excluded.next_run_at,
+             template = excluded.template,
+             strict_template = excluded.strict_template,
+             template_id = excluded.template_id,
+             template_revision = excluded.template_revision",
+        params![
+            batch.batch_id,
+            to_text(&batch.state),
+            batch.dry_run,
+            batch.total,
+            batch.completed,
+            batch.success,
+            batch.failed,
+            batch.skipped,
+            batch.created_by,
+            batch.approved_by,
+            batch.approved_at,
+            batch.created_at,
+            batch.schedule.as_ref().map(to_json),
+            batch.next_run_at,
+            batch.template,
+            batch.strict_template,
+            batch.template_id,
+            batch.template_revision,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 写入批次中第 position 个任务
+fn write_task(conn: &Connection, batch: &BatchStatus, position: usize) -> rusqlite::Result<()> {
+    let task = &batch.tasks[position];
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO tasks (id, batch_id, position, aid, video, content, status, error_msg, \
+             error_code, review_note, rendered, rpid, created_at, completed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+         ON CONFLICT (id) DO UPDATE SET
+             position = excluded.position,
+             content = excluded.content,
+             status = excluded.status,
+             error_msg = excluded.error_msg,
+             error_code = excluded.error_code,
+             review_note = excluded.review_note,
+             rendered = excluded.rendered,
+             rpid = excluded.rpid,
+             completed_at = excluded.completed_at",
+    )?;
+    stmt.execute(params![
+        task.id,
+        batch.batch_id,
+        position,
+        task.video.aid,
+        to_json(&task.video),
+        task.content,
+        to_text(&task.status),
+        task.error_msg,
+        task.error_code,
+        task.review_note,
+        task.rendered,
+        task.rpid,
+        task.created_at,
+        task.completed_at,
+    ])?;
+    Ok(())
+}
+
+/// 加载所有批次 (按创建时间，无法读取的批次和任务会被跳过并记录问题)
+pub fn load_batches() -> Result<Vec<BatchStatus>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT batch_id, state, dry_run, total, completed, success, failed, skipped, \
+                 created_by, approved_by, approved_at, created_at, schedule, next_run_at, \
+                 template, strict_template, template_id, template_revision
+             FROM batches ORDER BY created_at",
+        )?;
+        let mut batches = db::collect_rows("batches", stmt.query_map([], batch_from_row)?);
+
+        let index: HashMap<String, usize> = batches
+            .iter()
+            .enumerate()
+            .map(|(i, b)| (b.batch_id.clone(), i))
+            .collect();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, batch_id, video, content, status, error_msg, error_code, review_note, \
+                 rendered, rpid, created_at, completed_at
+             FROM tasks ORDER BY batch_id, position",
+        )?;
+        let tasks = db::collect_rows("tasks", stmt.query_map([], task_from_row)?);
+        for (batch_id, task) in tasks {
+            if let Some(&i) = index.get(&batch_id) {
+                batches[i].tasks.push(task);
+            }
+        }
+
+        Ok(batches)
+    })
+}
+
+/// 保存批次及其全部任务 (新增或覆盖)
+pub fn save_batch(batch: &BatchStatus) -> Result<(), String> {
+    db::with_conn(|conn| {
+        let tx = conn.transaction()?;
+        upsert(&tx, batch)?;
+        tx.commit()
+    })
+}
+
+/// 保存批次的状态和统计，以及第 `task` 个任务 (执行过程中只写入变化的行)
+pub fn save_progress(batch: &BatchStatus, task: Option<usize>) -> Result<(), String> {
+    db::with_conn(|conn| {
+        let tx = conn.transaction()?;
+        write_batch(&tx, batch)?;
+        if let Some(position) = task {
+            write_task(&tx, batch, position)?;
+        }
+        tx.commit()
+    })
+}
+
+/// 删除批次及其任务
+pub fn delete_batch(batch_id: &str) -> Result<(), String> {
+    db::with_conn(|conn| {
+        conn.execute("DELETE FROM batches WHERE batch_id = ?1", [batch_id])?;
+        Ok(())
+    })
+}
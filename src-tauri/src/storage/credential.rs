@@ -1,52 +1,60 @@
-use std::fs;
-use std::path::PathBuf;
+use rusqlite::{params, Connection, OptionalExtension};
 
 use crate::models::LoginCredential;
 
-use super::{ensure_dir, get_app_data_dir};
-
-/// 获取凭证文件路径
-fn get_credential_path() -> PathBuf {
-    get_app_data_dir().join("credential.json")
+use super::db;
+
+/// 写入登录凭证 (只保留一份)
+pub(super) fn write(conn: &Connection, credential: &LoginCredential) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO credential (id, sessdata, bili_jct, dedeuserid, expires_at)
+         VALUES (1, ?1, ?2, ?3, ?4)
+         ON CONFLICT (id) DO UPDATE SET
+             sessdata = excluded.sessdata,
+             bili_jct = excluded.bili_jct,
+             dedeuserid = excluded.dedeuserid,
+             expires_at = excluded.expires_at",
+        params![
+            credential.sessdata,
+            credential.bili_jct,
+            credential.dedeuserid,
+            credential.expires_at,
+        ],
+    )?;
+    Ok(())
 }
 
 /// 保存登录凭证
 pub fn save_credential(credential: &LoginCredential) -> Result<(), String> {
-    let path = get_credential_path();
-    ensure_dir(&path).map_err(|e| format!("创建目录失败: {}", e))?;
-
-    let json = serde_json::to_string_pretty(credential)
-        .map_err(|e| format!("序列化失败: {}", e))?;
+    db::with_conn(|conn| write(conn, credential))?;
 
-    fs::write(&path, json).map_err(|e| format!("写入文件失败: {}", e))?;
-
-    log::info!("凭证已保存到 {:?}", path);
+    log::info!("凭证已保存到数据库");
     Ok(())
 }
 
 /// 加载登录凭证
 pub fn load_credential() -> Result<Option<LoginCredential>, String> {
-    let path = get_credential_path();
-
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let json = fs::read_to_string(&path).map_err(|e| format!("读取文件失败: {}", e))?;
-
-    let credential: LoginCredential =
-        serde_json::from_str(&json).map_err(|e| format!("解析失败: {}", e))?;
-
-    Ok(Some(credential))
+    db::with_conn(|conn| {
+        conn.query_row(
+            "SELECT sessdata, bili_jct, dedeuserid, expires_at FROM credential WHERE id = 1",
+            [],
+            |row| {
+                Ok(LoginCredential {
+                    sessdata: row.get(0)?,
+                    bili_jct: row.get(1)?,
+                    dedeuserid: row.get(2)?,
+                    expires_at: row.get(3)?,
+                })
+            },
+        )
+        .optional()
+    })
 }
 
 /// 删除登录凭证
 pub fn delete_credential() -> Result<(), String> {
-    let path = get_credential_path();
-
-    if path.exists() {
-        fs::remove_file(&path).map_err(|e| format!("删除文件失败: {}", e))?;
-    }
-
-    Ok(())
+    db::with_conn(|conn| {
+        conn.execute("DELETE FROM credential", [])?;
+        Ok(())
+    })
 }
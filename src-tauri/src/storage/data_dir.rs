@@ -0,0 +1,272 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+
+use crate::models::{DataDirSource, DataLocation};
+
+/// 指定数据目录的环境变量
+const DATA_DIR_ENV: &str = "BILI_COMMENT_DATA_DIR";
+
+/// 启用便携模式的环境变量 (值为 1 或 true)
+const PORTABLE_ENV: &str = "BILI_COMMENT_PORTABLE";
+
+/// 指定数据目录的命令行参数 (`--data-dir <路径>` 或 `--data-dir=<路径>`)
+const DATA_DIR_FLAG: &str = "--data-dir";
+
+/// 启用便携模式的命令行参数
+const PORTABLE_FLAG: &str = "--portable";
+
+/// 可执行文件旁存在该文件时自动启用便携模式
+const PORTABLE_MARKER: &str = "portable";
+
+/// 便携模式下数据目录相对可执行文件所在目录的名称
+const PORTABLE_DIR_NAME: &str = "data";
+
+/// 默认数据目录名称 (位于系统数据目录下)
+const DEFAULT_DIR_NAME: &str = "bili-comment";
+
+/// 启动时确定的数据目录，运行期间不变
+static DATA_LOCATION: Lazy<DataLocation> = Lazy::new(|| {
+    let location = resolve(&StartupEnv::current());
+    log::info!("数据目录: {:?} ({:?})", location.path, location.source);
+    location
+});
+
+/// 确定数据目录所需的启动环境
+struct StartupEnv {
+    /// 命令行参数 (不含程序路径)
+    args: Vec<OsString>,
+    /// 环境变量 `BILI_COMMENT_DATA_DIR` 的值
+    data_dir_var: Option<OsString>,
+    /// 环境变量 `BILI_COMMENT_PORTABLE` 是否开启
+    portable_var: bool,
+    /// 可执行文件所在目录
+    exe_dir: Option<PathBuf>,
+    /// 当前工作目录，用于解析相对路径
+    current_dir: Option<PathBuf>,
+    /// 系统数据目录
+    system_data_dir: Option<PathBuf>,
+}
+
+impl StartupEnv {
+    /// 读取当前进程的启动环境
+    fn current() -> Self {
+        Self {
+            args: std::env::args_os().skip(1).collect(),
+            data_dir_var: std::env::var_os(DATA_DIR_ENV),
+            portable_var: env_enabled(PORTABLE_ENV),
+            exe_dir: exe_dir(),
+            current_dir: std::env::current_dir().ok(),
+            system_data_dir: dirs::data_dir(),
+        }
+    }
+
+    /// 可执行文件旁是否存在便携模式标记文件
+    fn has_portable_marker(&self) -> bool {
+        self.exe_dir
+            .as_ref()
+            .is_some_and(|dir| dir.join(PORTABLE_MARKER).exists())
+    }
+}
+
+/// 从命令行参数中读取 `--data-dir`
+fn data_dir_arg(args: &[OsString]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let text = arg.to_string_lossy();
+        if text == DATA_DIR_FLAG {
+            return iter.next().map(PathBuf::from);
+        }
+        if let Some(value) = text
+            .strip_prefix(DATA_DIR_FLAG)
+            .and_then(|v| v.strip_prefix('='))
+        {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+/// 环境变量是否为开启状态
+fn env_enabled(name: &str) -> bool {
+    std::env::var(name)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// 可执行文件所在目录
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+}
+
+/// 相对路径按工作目录解析为绝对路径
+fn absolute(path: PathBuf, current_dir: Option<&Path>) -> PathBuf {
+    if path.is_absolute() {
+        return path;
+    }
+    current_dir.map(|cwd| cwd.join(&path)).unwrap_or(path)
+}
+
+/// 确定数据目录
+///
+/// 优先级: 命令行 `--data-dir` > 环境变量 > 便携模式 > 系统数据目录
+fn resolve(env: &StartupEnv) -> DataLocation {
+    let current_dir = env.current_dir.as_deref();
+
+    if let Some(path) = data_dir_arg(&env.args) {
+        return DataLocation {
+            path: absolute(path, current_dir),
+            source: DataDirSource::CommandLine,
+        };
+    }
+
+    if let Some(path) = env.data_dir_var.as_ref().filter(|v| !v.is_empty()) {
+        return DataLocation {
+            path: absolute(PathBuf::from(path), current_dir),
+            source: DataDirSource::Environment,
+        };
+    }
+
+    let portable_requested = env.args.iter().any(|a| a == PORTABLE_FLAG) || env.portable_var;
+    if let Some(dir) = &env.exe_dir {
+        if portable_requested || env.has_portable_marker() {
+            return DataLocation {
+                path: dir.join(PORTABLE_DIR_NAME),
+                source: DataDirSource::Portable,
+            };
+        }
+    }
+
+    let base = env
+        .system_data_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    DataLocation {
+        path: base.join(DEFAULT_DIR_NAME),
+        source: DataDirSource::Default,
+    }
+}
+
+/// 获取应用数据目录
+pub fn get_app_data_dir() -> PathBuf {
+    DATA_LOCATION.path.clone()
+}
+
+/// 获取数据目录及其来源
+pub fn data_location() -> DataLocation {
+    DATA_LOCATION.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn app_dir() -> PathBuf {
+        PathBuf::from("nonexistent-app")
+    }
+
+    fn cwd() -> PathBuf {
+        PathBuf::from("work")
+    }
+
+    /// 没有任何配置的启动环境 (可执行文件目录下没有标记文件)
+    fn env(args: &[&str]) -> StartupEnv {
+        StartupEnv {
+            args: args.iter().map(OsString::from).collect(),
+            data_dir_var: None,
+            portable_var: false,
+            exe_dir: Some(app_dir()),
+            current_dir: Some(cwd()),
+            system_data_dir: Some(PathBuf::from("system-data")),
+        }
+    }
+
+    fn resolved(env: &StartupEnv) -> (PathBuf, DataDirSource) {
+        let location = resolve(env);
+        (location.path, location.source)
+    }
+
+    #[test]
+    fn parses_data_dir_arg_forms() {
+        let args = |a: &[&str]| a.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(
+            data_dir_arg(&args(&["--data-dir", "a"])),
+            Some(PathBuf::from("a"))
+        );
+        assert_eq!(
+            data_dir_arg(&args(&["--portable", "--data-dir=b"])),
+            Some(PathBuf::from("b"))
+        );
+        assert_eq!(data_dir_arg(&args(&["--data-dir"])), None);
+        assert_eq!(data_dir_arg(&args(&["--data-directory=c"])), None);
+    }
+
+    #[test]
+    fn defaults_to_system_data_dir() {
+        assert_eq!(
+            resolved(&env(&[])),
+            (
+                PathBuf::from("system-data").join(DEFAULT_DIR_NAME),
+                DataDirSource::Default
+            )
+        );
+    }
+
+    #[test]
+    fn command_line_wins_over_everything() {
+        let mut env = env(&["--portable", "--data-dir=from-arg"]);
+        env.data_dir_var = Some("from-env".into());
+        env.portable_var = true;
+        // 相对路径按工作目录解析
+        assert_eq!(
+            resolved(&env),
+            (cwd().join("from-arg"), DataDirSource::CommandLine)
+        );
+    }
+
+    #[test]
+    fn environment_wins_over_portable() {
+        let mut env = env(&["--portable"]);
+        env.data_dir_var = Some("from-env".into());
+        assert_eq!(
+            resolved(&env),
+            (cwd().join("from-env"), DataDirSource::Environment)
+        );
+
+        // 空值视为未设置
+        env.data_dir_var = Some("".into());
+        assert_eq!(resolved(&env).1, DataDirSource::Portable);
+    }
+
+    #[test]
+    fn portable_by_flag_or_variable() {
+        let portable = (app_dir().join(PORTABLE_DIR_NAME), DataDirSource::Portable);
+        assert_eq!(resolved(&env(&["--portable"])), portable);
+
+        let mut env = env(&[]);
+        env.portable_var = true;
+        assert_eq!(resolved(&env), portable);
+    }
+
+    #[test]
+    fn portable_by_marker_file() {
+        let dir = std::env::temp_dir().join(format!("bili-comment-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(PORTABLE_MARKER), "").unwrap();
+
+        let mut env = env(&[]);
+        env.exe_dir = Some(dir.clone());
+        let result = resolved(&env);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            result,
+            (dir.join(PORTABLE_DIR_NAME), DataDirSource::Portable)
+        );
+    }
+}
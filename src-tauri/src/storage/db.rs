@@ -0,0 +1,269 @@
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rusqlite::types::Type;
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::models::StorageIssue;
+
+use super::{ensure_dir, get_app_data_dir, json_import, push_issue};
+
+/// 数据库迁移脚本，第 N 个脚本执行后 schema 版本为 N (记录在 `PRAGMA user_version`)
+///
+/// 已发布的脚本不能修改，结构变更只能追加新脚本
+pub(super) const MIGRATIONS: &[&str] = &[
+    // v1: 模板、批次、任务、评论记录、设置、登录凭证
+    "
+    CREATE TABLE templates (
+        id           TEXT PRIMARY KEY,
+        name         TEXT NOT NULL,
+        content      TEXT NOT NULL,
+        category     TEXT,
+        tags         TEXT NOT NULL DEFAULT '[]',
+        created_at   INTEGER NOT NULL,
+        updated_at   INTEGER NOT NULL,
+        last_used_at INTEGER,
+        revision     INTEGER NOT NULL DEFAULT 1
+    );
+
+    CREATE TABLE template_revisions (
+        template_id TEXT NOT NULL,
+        revision    INTEGER NOT NULL,
+        name        TEXT NOT NULL,
+        content     TEXT NOT NULL,
+        created_at  INTEGER NOT NULL,
+        PRIMARY KEY (template_id, revision)
+    );
+
+    CREATE TABLE batches (
+        batch_id          TEXT PRIMARY KEY,
+        state             TEXT NOT NULL,
+        dry_run           INTEGER NOT NULL DEFAULT 0,
+        total             INTEGER NOT NULL,
+        completed         INTEGER NOT NULL,
+        success           INTEGER NOT NULL,
+        failed            INTEGER NOT NULL,
+        skipped           INTEGER NOT NULL,
+        created_by        TEXT,
+        approved_by       TEXT,
+        approved_at       INTEGER,
+        created_at        INTEGER NOT NULL,
+        schedule          TEXT,
+        next_run_at       INTEGER,
+        template          TEXT NOT NULL,
+        strict_template   INTEGER NOT NULL DEFAULT 0,
+        template_id       TEXT,
+        template_revision INTEGER
+    );
+
+    CREATE TABLE tasks (
+        id           TEXT PRIMARY KEY,
+        batch_id     TEXT NOT NULL REFERENCES batches (batch_id) ON DELETE CASCADE,
+        position     INTEGER NOT NULL,
+        aid          INTEGER NOT NULL,
+        video        TEXT NOT NULL,
+        content      TEXT NOT NULL,
+        status       TEXT NOT NULL,
+        error_msg    TEXT,
+        error_code   INTEGER,
+        review_note  TEXT,
+        rendered     INTEGER NOT NULL DEFAULT 0,
+        rpid         INTEGER,
+        created_at   INTEGER NOT NULL,
+        completed_at INTEGER
+    );
+    CREATE INDEX idx_tasks_batch ON tasks (batch_id, position);
+
+    CREATE TABLE comment_ledger (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        aid         INTEGER NOT NULL,
+        bvid        TEXT NOT NULL,
+        rpid        INTEGER,
+        content     TEXT NOT NULL,
+        batch_id    TEXT,
+        template_id TEXT,
+        sent_at     INTEGER NOT NULL
+    );
+    CREATE INDEX idx_ledger_aid ON comment_ledger (aid);
+    CREATE INDEX idx_ledger_sent_at ON comment_ledger (sent_at);
+
+    CREATE TABLE settings (
+        key   TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+
+    CREATE TABLE credential (
+        id         INTEGER PRIMARY KEY CHECK (id = 1),
+        sessdata   TEXT NOT NULL,
+        bili_jct   TEXT NOT NULL,
+        dedeuserid TEXT NOT NULL,
+        expires_at INTEGER NOT NULL
+    );
+    ",
+    // v2: 视频筛选规则预设
+    "
+    CREATE TABLE filter_presets (
+        id         TEXT PRIMARY KEY,
+        name       TEXT NOT NULL,
+        rules      TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    ",
+    // v3: UP主/视频屏蔽名单和允许名单
+    "
+    CREATE TABLE access_list (
+        kind       TEXT NOT NULL,
+        target     TEXT NOT NULL,
+        value      TEXT NOT NULL,
+        reason     TEXT,
+        created_at INTEGER NOT NULL,
+        PRIMARY KEY (kind, target, value)
+    );
+    ",
+    // v4: 名单条目的更新时间
+    "
+    ALTER TABLE access_list ADD COLUMN updated_at INTEGER NOT NULL DEFAULT 0;
+    UPDATE access_list SET updated_at = created_at;
+    ",
+    // v5: 本地敏感词 (导入旧版 sensitive_words.json)
+    "
+    CREATE TABLE sensitive_words (
+        position INTEGER NOT NULL,
+        kind     TEXT NOT NULL,
+        pattern  TEXT NOT NULL
+    );
+    ",
+];
+
+/// 数据库连接 (首次使用时打开并执行迁移)
+static DB: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+/// 获取数据库文件路径
+fn get_db_path() -> PathBuf {
+    get_app_data_dir().join("bili-comment.db")
+}
+
+/// 执行未应用的迁移
+///
+/// 创建表结构的迁移 (v1、v5) 会在同一事务中导入旧版 JSON 文件的数据
+fn migrate(conn: &mut Connection) -> Result<(), String> {
+    let current: u32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(|e| format!("读取数据库版本失败: {}", e))?;
+    let current = current as usize;
+
+    if current > MIGRATIONS.len() {
+        return Err(format!(
+            "数据库版本 {} 高于当前程序支持的版本 {}，请升级程序",
+            current,
+            MIGRATIONS.len()
+        ));
+    }
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+        let version = index as u32 + 1;
+        let mut apply = || -> rusqlite::Result<usize> {
+            let tx = conn.transaction()?;
+            tx.execute_batch(sql)?;
+            let imported = json_import::import(&tx, version)?;
+            tx.pragma_update(None, "user_version", version)?;
+            tx.commit()?;
+            Ok(imported)
+        };
+        let imported = apply().map_err(|e| format!("数据库迁移到版本 {} 失败: {}", version, e))?;
+        if imported > 0 {
+            json_import::finish_import(version);
+        }
+        log::info!("数据库已迁移到版本 {}", version);
+    }
+
+    Ok(())
+}
+
+/// 打开数据库并执行迁移
+fn open() -> Result<Connection, String> {
+    let path = get_db_path();
+    ensure_dir(&path).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let mut conn = Connection::open(&path).map_err(|e| format!("打开数据库失败: {}", e))?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("打开数据库失败: {}", e))?;
+
+    migrate(&mut conn)?;
+
+    log::info!("数据库已打开: {:?}", path);
+    Ok(conn)
+}
+
+/// 初始化数据库 (应用启动时调用，保证迁移和导入在其他命令之前完成)
+pub fn init() -> Result<(), String> {
+    with_conn(|_| Ok(()))
+}
+
+/// 使用数据库连接执行操作
+pub fn with_conn<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let mut db = DB.lock();
+    if db.is_none() {
+        *db = Some(open()?);
+    }
+
+    let conn = db.as_mut().expect("数据库连接已初始化");
+    f(conn).map_err(|e| format!("数据库操作失败: {}", e))
+}
+
+/// 记录数据库中某张表的读取问题，供前端提示用户
+pub fn report_issue(table: &str, error: &str) {
+    log::error!("读取数据表失败: {}, {}", table, error);
+    push_issue(StorageIssue {
+        file: format!("{} ({})", get_db_path().display(), table),
+        error: error.to_string(),
+        quarantined_to: None,
+        restored: false,
+        occurred_at: chrono::Utc::now().timestamp(),
+    });
+}
+
+/// 收集查询结果: 跳过无法读取的行并记录问题，单行损坏不影响同一张表的其他数据
+pub(super) fn collect_rows<T>(
+    table: &str,
+    rows: impl Iterator<Item = rusqlite::Result<T>>,
+) -> Vec<T> {
+    rows.enumerate()
+        .filter_map(|(index, row)| match row {
+            Ok(value) => Some(value),
+            Err(e) => {
+                report_issue(table, &format!("第 {} 行读取失败: {}", index + 1, e));
+                None
+            }
+        })
+        .collect()
+}
+
+/// 将序列化为字符串的枚举 (如任务状态) 转为数据库中的文本
+pub(super) fn to_text<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        _ => String::new(),
+    }
+}
+
+/// 从数据库中的文本还原枚举
+pub(super) fn from_text<T: DeserializeOwned>(text: String) -> rusqlite::Result<T> {
+    serde_json::from_value(serde_json::Value::String(text))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(e)))
+}
+
+/// 将结构体序列化为 JSON 文本存入数据库
+pub(super) fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// 从数据库中的 JSON 文本还原结构体
+pub(super) fn from_json<T: DeserializeOwned>(json: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(e)))
+}
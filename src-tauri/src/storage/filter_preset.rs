@@ -0,0 +1,71 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::models::FilterPreset;
+
+use super::db::{self, from_json, to_json};
+
+const SELECT_PRESET: &str = "SELECT id, name, rules, created_at, updated_at FROM filter_presets";
+
+/// 从查询结果行构造筛选预设
+fn from_row(row: &Row) -> rusqlite::Result<FilterPreset> {
+    let rules: String = row.get(2)?;
+    Ok(FilterPreset {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        rules: from_json(&rules)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+/// 写入筛选预设 (ID 已存在时覆盖)
+pub(super) fn upsert(conn: &Connection, preset: &FilterPreset) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO filter_presets (id, name, rules, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (id) DO UPDATE SET
+             name = excluded.name,
+             rules = excluded.rules,
+             created_at = excluded.created_at,
+             updated_at = excluded.updated_at",
+        params![
+            preset.id,
+            preset.name,
+            to_json(&preset.rules),
+            preset.created_at,
+            preset.updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 加载所有筛选预设 (按创建顺序，无法读取的预设会被跳过并记录问题)
+pub fn load_presets() -> Result<Vec<FilterPreset>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("{} ORDER BY created_at, rowid", SELECT_PRESET))?;
+        let rows = stmt.query_map([], from_row)?;
+        Ok(db::collect_rows("filter_presets", rows))
+    })
+}
+
+/// 获取指定筛选预设
+pub fn get_preset(id: &str) -> Result<Option<FilterPreset>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("{} WHERE id = ?1", SELECT_PRESET))?;
+        let mut rows = stmt.query_map([id], from_row)?;
+        rows.next().transpose()
+    })
+}
+
+/// 保存筛选预设 (新增或覆盖)
+pub fn save_preset(preset: &FilterPreset) -> Result<(), String> {
+    db::with_conn(|conn| upsert(conn, preset))
+}
+
+/// 删除筛选预设，返回是否存在
+pub fn delete_preset(id: &str) -> Result<bool, String> {
+    db::with_conn(|conn| {
+        let count = conn.execute("DELETE FROM filter_presets WHERE id = ?1", [id])?;
+        Ok(count > 0)
+    })
+}
@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+
+use crate::models::{
+    BatchStatus, CommentLedgerEntry, CommentTemplate, LoginCredential, SensitiveWord, Settings,
+    TaskStatus, TemplateRevision,
+};
+
+use super::{backup_path, batch, credential, get_app_data_dir, ledger, read_json, settings};
+use super::{sensitive_words, template, template_revision};
+
+/// 旧版 JSON 数据文件路径
+fn json_path(name: &str) -> PathBuf {
+    get_app_data_dir().join(name)
+}
+
+/// 导入完成后将 JSON 文件重命名为 `.imported` 保留
+fn mark_imported(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".imported");
+    if let Err(e) = fs::rename(path, path.with_file_name(name)) {
+        log::warn!("重命名已导入的文件失败: {:?}, {}", path, e);
+    }
+}
+
+/// 已成功发送的任务转为评论记录
+fn ledger_entries(batch: &BatchStatus) -> impl Iterator<Item = CommentLedgerEntry> + '_ {
+    batch
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Success)
+        .map(|t| CommentLedgerEntry {
+            aid: t.video.aid,
+            bvid: t.video.bvid.clone(),
+            rpid: t.rpid,
+            content: t.content.clone(),
+            batch_id: Some(batch.batch_id.clone()),
+            template_id: batch.template_id.clone(),
+            sent_at: t.completed_at.unwrap_or(batch.created_at),
+        })
+}
+
+/// 导入迁移到第 `version` 版时需要的旧版 JSON 数据，返回导入的条数
+///
+/// 在创建表结构的迁移事务中执行，导入失败时整个迁移回滚，下次启动重试
+pub(super) fn import(tx: &Connection, version: u32) -> rusqlite::Result<usize> {
+    match version {
+        1 => import_core(tx),
+        5 => import_sensitive_words(tx),
+        _ => Ok(0),
+    }
+}
+
+/// 导入旧版 JSON 文件中的模板、批次、设置和登录凭证
+fn import_core(tx: &Connection) -> rusqlite::Result<usize> {
+    let mut count = 0;
+
+    let templates: Vec<CommentTemplate> =
+        read_json(&json_path("templates.json")).unwrap_or_default();
+    for t in &templates {
+        template::upsert(tx, t)?;
+    }
+    count += templates.len();
+
+    let revisions: Vec<TemplateRevision> =
+        read_json(&json_path("template_revisions.json")).unwrap_or_default();
+    for r in &revisions {
+        template_revision::insert(tx, r)?;
+    }
+    count += revisions.len();
+
+    let batches: Vec<BatchStatus> = read_json(&json_path("batches.json")).unwrap_or_default();
+    for b in &batches {
+        batch::upsert(tx, b)?;
+        for entry in ledger_entries(b) {
+            ledger::insert(tx, &entry)?;
+        }
+    }
+    count += batches.len();
+
+    if let Some(s) = read_json::<Settings>(&json_path("settings.json")) {
+        settings::write(tx, &s)?;
+        count += 1;
+    }
+
+    if let Some(c) = read_json::<LoginCredential>(&json_path("credential.json")) {
+        credential::write(tx, &c)?;
+        count += 1;
+    }
+
+    if count > 0 {
+        log::info!("已从 JSON 文件导入 {} 条数据到数据库", count);
+    }
+    Ok(count)
+}
+
+/// 导入旧版 JSON 文件中的本地敏感词
+fn import_sensitive_words(tx: &Connection) -> rusqlite::Result<usize> {
+    let words: Vec<SensitiveWord> =
+        read_json(&json_path("sensitive_words.json")).unwrap_or_default();
+    sensitive_words::replace(tx, &words)?;
+    if !words.is_empty() {
+        log::info!("已从 JSON 文件导入 {} 个敏感词到数据库", words.len());
+    }
+    Ok(words.len())
+}
+
+/// 导入事务提交后处理旧文件: 数据文件重命名保留，登录凭证不保留明文副本
+pub(super) fn finish_import(version: u32) {
+    if version == 5 {
+        mark_imported(&json_path("sensitive_words.json"));
+        return;
+    }
+
+    for name in [
+        "templates.json",
+        "template_revisions.json",
+        "batches.json",
+        "settings.json",
+    ] {
+        mark_imported(&json_path(name));
+    }
+
+    let path = json_path("credential.json");
+    for path in [backup_path(&path), path] {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                log::warn!("删除旧版凭证文件失败: {:?}, {}", path, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::models::CommentLedgerEntry;
+
+use super::db;
+
+/// 从查询结果行构造评论记录
+fn from_row(row: &Row) -> rusqlite::Result<CommentLedgerEntry> {
+    Ok(CommentLedgerEntry {
+        aid: row.get(0)?,
+        bvid: row.get(1)?,
+        rpid: row.get(2)?,
+        content: row.get(3)?,
+        batch_id: row.get(4)?,
+        template_id: row.get(5)?,
+        sent_at: row.get(6)?,
+    })
+}
+
+/// 写入一条已发送评论记录
+pub(super) fn insert(conn: &Connection, entry: &CommentLedgerEntry) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO comment_ledger (aid, bvid, rpid, content, batch_id, template_id, sent_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            entry.aid,
+            entry.bvid,
+            entry.rpid,
+            entry.content,
+            entry.batch_id,
+            entry.template_id,
+            entry.sent_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 记录已发送的评论 (用于去重和每日配额，清除批次后仍保留)
+pub fn record_comment(entry: &CommentLedgerEntry) -> Result<(), String> {
+    db::with_conn(|conn| insert(conn, entry))
+}
+
+/// 是否已在该视频下成功评论过
+pub fn has_commented(aid: u64) -> Result<bool, String> {
+    db::with_conn(|conn| {
+        conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM comment_ledger WHERE aid = ?1)",
+            [aid],
+            |row| row.get(0),
+        )
+    })
+}
+
+/// 指定时刻之后发送的评论数 (不含指定批次发送的评论)
+pub fn count_since(since: i64, exclude_batch: &str) -> Result<usize, String> {
+    db::with_conn(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM comment_ledger WHERE sent_at >= ?1 AND batch_id IS NOT ?2",
+            params![since, exclude_batch],
+            |row| row.get(0),
+        )
+    })
+}
+
+/// 加载所有评论记录 (按发送顺序)
+pub fn load_entries() -> Result<Vec<CommentLedgerEntry>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT aid, bvid, rpid, content, batch_id, template_id, sent_at
+             FROM comment_ledger ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], from_row)?;
+        rows.collect()
+    })
+}
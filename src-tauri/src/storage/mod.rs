@@ -1,20 +1,188 @@
 // 数据存储模块
 
+pub mod access_list;
+pub mod backup;
+pub mod batch;
 pub mod credential;
+mod data_dir;
+pub mod db;
+pub mod filter_preset;
+mod json_import;
+pub mod ledger;
+pub mod sensitive_words;
+pub mod settings;
 pub mod template;
+pub mod template_exchange;
+pub mod template_revision;
 
-use std::path::PathBuf;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-/// 获取应用数据目录
-pub fn get_app_data_dir() -> PathBuf {
-    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-    base.join("bili-comment")
-}
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, ReentrantMutex, ReentrantMutexGuard};
+use serde::de::DeserializeOwned;
+
+use crate::models::StorageIssue;
+
+pub use data_dir::{data_location, get_app_data_dir};
+
+/// 存储读写锁，保证 "读取-修改-保存" 过程不被其他命令打断
+///
+/// 可重入: 持有锁时调用的 load/save 函数可以再次加锁
+static STORAGE_LOCK: Lazy<ReentrantMutex<()>> = Lazy::new(|| ReentrantMutex::new(()));
+
+/// 本次运行中遇到的存储文件问题，供前端展示
+static STORAGE_ISSUES: Lazy<Mutex<Vec<StorageIssue>>> = Lazy::new(|| Mutex::new(Vec::new()));
 
 /// 确保目录存在
-pub fn ensure_dir(path: &PathBuf) -> std::io::Result<()> {
+pub fn ensure_dir(path: &Path) -> std::io::Result<()> {
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        fs::create_dir_all(parent)?;
     }
     Ok(())
 }
+
+/// 获取存储锁，在返回的守卫被释放前其他线程无法读写存储文件
+pub fn lock() -> ReentrantMutexGuard<'static, ()> {
+    STORAGE_LOCK.lock()
+}
+
+/// 在文件名后追加后缀 (如 `templates.json` -> `templates.json.bak`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// 备份文件路径
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+/// 原子写入文件: 先写入临时文件并刷盘，再重命名覆盖原文件
+///
+/// 原文件存在时先复制为 `.bak` 备份，写入中途崩溃不会损坏原文件
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    let _guard = lock();
+
+    ensure_dir(path).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let tmp_path = with_suffix(path, ".tmp");
+    let write_tmp = || -> std::io::Result<()> {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    };
+    if let Err(e) = write_tmp() {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(format!("写入文件失败: {}", e));
+    }
+
+    if path.exists() {
+        if let Err(e) = fs::copy(path, backup_path(path)) {
+            log::warn!("备份文件失败: {:?}, {}", path, e);
+        }
+    }
+
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("写入文件失败: {}", e)
+    })
+}
+
+/// 读取并解析 JSON 文件
+fn parse_file<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("读取文件失败: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("解析失败: {}", e))
+}
+
+/// 将损坏的文件重命名隔离 (如 `templates.json.corrupt-20240101-120000`)
+fn quarantine(path: &Path) -> Option<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let target = with_suffix(path, &format!(".corrupt-{}", stamp));
+    match fs::rename(path, &target) {
+        Ok(()) => Some(target),
+        Err(e) => {
+            log::error!("隔离损坏文件失败: {:?}, {}", path, e);
+            None
+        }
+    }
+}
+
+/// 加载 JSON 存储文件 (文件不存在时返回 None)
+///
+/// 文件无法读取或解析时不会静默丢弃: 先将其隔离保留，再尝试从 `.bak` 备份恢复，
+/// 并记录问题供前端提示用户
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    if !path.exists() {
+        return None;
+    }
+
+    let error = match parse_file(path) {
+        Ok(value) => return Some(value),
+        Err(e) => e,
+    };
+
+    // 其他线程可能已完成恢复，加锁后重新检查
+    let _guard = lock();
+    if let Ok(value) = parse_file(path) {
+        return Some(value);
+    }
+    log::error!("存储文件损坏: {:?}, {}", path, error);
+
+    let quarantined_to = quarantine(path);
+
+    let backup = backup_path(path);
+    let restored = if backup.exists() {
+        match parse_file::<T>(&backup) {
+            Ok(value) => match fs::copy(&backup, path) {
+                Ok(_) => {
+                    log::warn!("已从备份恢复: {:?}", path);
+                    Some(value)
+                }
+                Err(e) => {
+                    log::error!("从备份恢复失败: {:?}, {}", path, e);
+                    None
+                }
+            },
+            Err(e) => {
+                log::error!("备份文件也无法使用: {:?}, {}", backup, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    push_issue(StorageIssue {
+        file: path.display().to_string(),
+        error,
+        quarantined_to: quarantined_to.map(|p| p.display().to_string()),
+        restored: restored.is_some(),
+        occurred_at: chrono::Utc::now().timestamp(),
+    });
+
+    restored
+}
+
+/// 记录存储问题 (同一文件的相同错误只记录一次，避免重复加载时刷屏)
+fn push_issue(issue: StorageIssue) {
+    let mut issues = STORAGE_ISSUES.lock();
+    if !issues
+        .iter()
+        .any(|i| i.file == issue.file && i.error == issue.error)
+    {
+        issues.push(issue);
+    }
+}
+
+/// 获取本次运行中遇到的存储文件问题
+pub fn storage_issues() -> Vec<StorageIssue> {
+    STORAGE_ISSUES.lock().clone()
+}
+
+/// 清除已提示的存储文件问题
+pub fn clear_storage_issues() {
+    STORAGE_ISSUES.lock().clear();
+}
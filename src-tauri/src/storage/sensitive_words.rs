@@ -0,0 +1,104 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::models::{SensitiveWord, WordKind};
+
+use super::db::{self, from_text, to_text};
+
+/// 从查询结果行构造敏感词
+fn from_row(row: &Row) -> rusqlite::Result<SensitiveWord> {
+    Ok(SensitiveWord {
+        kind: from_text(row.get(0)?)?,
+        pattern: row.get(1)?,
+    })
+}
+
+/// 用新的列表替换已保存的敏感词 (保持列表顺序)
+pub(super) fn replace(conn: &Connection, words: &[SensitiveWord]) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM sensitive_words", [])?;
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO sensitive_words (position, kind, pattern) VALUES (?1, ?2, ?3)",
+    )?;
+    for (position, word) in words.iter().enumerate() {
+        stmt.execute(params![position, to_text(&word.kind), word.pattern])?;
+    }
+    Ok(())
+}
+
+/// 解析文本格式的敏感词列表
+///
+/// 每行一个词，忽略空行和 `#` 开头的注释；以 `re:` 开头的行为正则表达式
+pub fn parse_word_list(text: &str) -> Vec<SensitiveWord> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.strip_prefix("re:") {
+            Some(pattern) => SensitiveWord {
+                pattern: pattern.trim().to_string(),
+                kind: WordKind::Regex,
+            },
+            None => SensitiveWord {
+                pattern: line.to_string(),
+                kind: WordKind::Plain,
+            },
+        })
+        .collect()
+}
+
+/// 加载敏感词列表 (无法读取的条目会被跳过并记录问题)
+pub fn load_sensitive_words() -> Result<Vec<SensitiveWord>, String> {
+    db::with_conn(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT kind, pattern FROM sensitive_words ORDER BY position")?;
+        let rows = stmt.query_map([], from_row)?;
+        Ok(db::collect_rows("sensitive_words", rows))
+    })
+}
+
+/// 保存敏感词列表
+pub fn save_sensitive_words(words: &[SensitiveWord]) -> Result<(), String> {
+    db::with_conn(|conn| {
+        let tx = conn.transaction()?;
+        replace(&tx, words)?;
+        tx.commit()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_and_regex_lines() {
+        let words = parse_word_list("# 注释\n\n  spam  \nre: 加\\s*微信\nre:\nrecord\n");
+        let parsed: Vec<(&str, WordKind)> =
+            words.iter().map(|w| (w.pattern.as_str(), w.kind)).collect();
+        assert_eq!(
+            parsed,
+            [
+                ("spam", WordKind::Plain),
+                ("加\\s*微信", WordKind::Regex),
+                ("", WordKind::Regex),
+                ("record", WordKind::Plain),
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_keeps_order() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(db::MIGRATIONS[4]).unwrap();
+
+        let load = |conn: &Connection| {
+            let mut stmt = conn
+                .prepare("SELECT kind, pattern FROM sensitive_words ORDER BY position")
+                .unwrap();
+            let rows = stmt.query_map([], from_row).unwrap();
+            rows.map(|w| w.unwrap().pattern).collect::<Vec<_>>()
+        };
+
+        replace(&conn, &parse_word_list("b\nre:a\n")).unwrap();
+        assert_eq!(load(&conn), ["b", "a"]);
+        replace(&conn, &parse_word_list("c\n")).unwrap();
+        assert_eq!(load(&conn), ["c"]);
+    }
+}
@@ -0,0 +1,142 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use rusqlite::{params, Connection};
+
+use crate::models::Settings;
+
+use super::db;
+
+/// 当前设置的内存缓存 (首次读取时从数据库加载，保存时更新)
+static CURRENT: Lazy<RwLock<Option<Settings>>> = Lazy::new(|| RwLock::new(None));
+
+/// 写入设置 (每个字段一行，值为 JSON)
+pub(super) fn write(conn: &Connection, settings: &Settings) -> rusqlite::Result<()> {
+    let serde_json::Value::Object(fields) = serde_json::to_value(settings).unwrap_or_default()
+    else {
+        return Ok(());
+    };
+
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+    )?;
+    for (key, value) in fields {
+        stmt.execute(params![key, value.to_string()])?;
+    }
+    Ok(())
+}
+
+/// 从各字段的值构造设置，缺少、无法解析或校验不通过的字段使用默认值
+fn from_fields(mut fields: serde_json::Map<String, serde_json::Value>) -> Settings {
+    let settings: Settings = match serde_json::from_value(serde_json::Value::Object(fields.clone()))
+    {
+        Ok(settings) => settings,
+        Err(e) => {
+            log::error!("解析设置失败，使用默认设置: {}", e);
+            return Settings::default();
+        }
+    };
+
+    let invalid = settings.invalid_fields();
+    if invalid.is_empty() {
+        return settings;
+    }
+
+    log::warn!("设置项 {:?} 的值无效，使用默认值", invalid);
+    for name in invalid {
+        fields.remove(name);
+    }
+    serde_json::from_value(serde_json::Value::Object(fields)).unwrap_or_default()
+}
+
+/// 加载设置 (缺少或无效的字段使用默认值)
+pub fn load_settings() -> Settings {
+    let result = db::with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut fields = serde_json::Map::new();
+        for row in rows {
+            let (key, value) = row?;
+            // 无法解析的值忽略，使用默认值
+            if let Ok(value) = serde_json::from_str(&value) {
+                fields.insert(key, value);
+            }
+        }
+        Ok(fields)
+    });
+
+    match result {
+        Ok(fields) => from_fields(fields),
+        Err(e) => {
+            log::error!("加载设置失败，使用默认设置: {}", e);
+            Settings::default()
+        }
+    }
+}
+
+/// 保存设置
+pub fn save_settings(settings: &Settings) -> Result<(), String> {
+    db::with_conn(|conn| write(conn, settings))?;
+    set_cached(settings);
+    Ok(())
+}
+
+/// 替换缓存中的设置 (设置在其他事务中写入后调用)
+pub(super) fn set_cached(settings: &Settings) {
+    *CURRENT.write() = Some(settings.clone());
+}
+
+/// 获取当前设置 (优先使用缓存，供请求时读取)
+pub fn current() -> Settings {
+    if let Some(settings) = CURRENT.read().as_ref() {
+        return settings.clone();
+    }
+
+    let mut cache = CURRENT.write();
+    cache.get_or_insert_with(load_settings).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn fields(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        match value {
+            serde_json::Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn invalid_fields_fall_back_to_defaults() {
+        let settings = from_fields(fields(json!({
+            "comment_interval_secs": 0,
+            "task_max_retries": 3,
+            "search_retry_delay_ms": u64::MAX,
+        })));
+        let defaults = Settings::default();
+        assert_eq!(
+            settings.comment_interval_secs,
+            defaults.comment_interval_secs
+        );
+        assert_eq!(
+            settings.search_retry_delay_ms,
+            defaults.search_retry_delay_ms
+        );
+        assert_eq!(settings.task_max_retries, 3);
+    }
+
+    #[test]
+    fn wrong_type_falls_back_to_defaults() {
+        let settings = from_fields(fields(json!({ "comment_interval_secs": "fast" })));
+        assert_eq!(
+            settings.comment_interval_secs,
+            Settings::default().comment_interval_secs
+        );
+    }
+}
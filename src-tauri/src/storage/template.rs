@@ -1,69 +1,214 @@
-use std::fs;
-use std::path::PathBuf;
+use rusqlite::{params, Connection, Row};
 
-use crate::models::CommentTemplate;
+use crate::i18n::{self, Msg};
+use crate::models::{CommentTemplate, TemplateFilter, TemplateSort};
 
-use super::{ensure_dir, get_app_data_dir};
+use super::db::{self, from_json, to_json};
 
-/// 获取模板文件路径
-fn get_templates_path() -> PathBuf {
-    get_app_data_dir().join("templates.json")
-}
+const SELECT_TEMPLATE: &str = "SELECT id, name, content, category, tags, created_at, updated_at, \
+     last_used_at, revision FROM templates";
 
-/// 加载所有模板
-pub fn load_templates() -> Vec<CommentTemplate> {
-    let path = get_templates_path();
+/// 从查询结果行构造模板
+fn from_row(row: &Row) -> rusqlite::Result<CommentTemplate> {
+    let tags: String = row.get(4)?;
+    Ok(CommentTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        content: row.get(2)?,
+        category: row.get(3)?,
+        tags: from_json(&tags)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        last_used_at: row.get(7)?,
+        revision: row.get(8)?,
+    })
+}
 
-    if !path.exists() {
-        return Vec::new();
-    }
+/// 写入模板 (ID 已存在时覆盖)
+pub(super) fn upsert(conn: &Connection, template: &CommentTemplate) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO templates (id, name, content, category, tags, created_at, updated_at, \
+             last_used_at, revision)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT (id) DO UPDATE SET
+             name = excluded.name,
+             content = excluded.content,
+             category = excluded.category,
+             tags = excluded.tags,
+             created_at = excluded.created_at,
+             updated_at = excluded.updated_at,
+             last_used_at = excluded.last_used_at,
+             revision = excluded.revision",
+        params![
+            template.id,
+            template.name,
+            template.content,
+            template.category,
+            to_json(&template.tags),
+            template.created_at,
+            template.updated_at,
+            template.last_used_at,
+            template.revision,
+        ],
+    )?;
+    Ok(())
+}
 
-    fs::read_to_string(&path)
-        .ok()
-        .and_then(|s| serde_json::from_str(&s).ok())
-        .unwrap_or_default()
+/// 加载所有模板 (按创建顺序，无法读取的模板会被跳过并记录问题)
+pub fn load_templates() -> Result<Vec<CommentTemplate>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!("{} ORDER BY created_at, rowid", SELECT_TEMPLATE))?;
+        let rows = stmt.query_map([], from_row)?;
+        Ok(db::collect_rows("templates", rows))
+    })
 }
 
-/// 保存所有模板
+/// 保存模板 (新增或覆盖，不会删除未列出的模板)
 pub fn save_templates(templates: &[CommentTemplate]) -> Result<(), String> {
-    let path = get_templates_path();
-    ensure_dir(&path).map_err(|e| format!("创建目录失败: {}", e))?;
-
-    let json = serde_json::to_string_pretty(templates)
-        .map_err(|e| format!("序列化失败: {}", e))?;
-
-    fs::write(&path, json).map_err(|e| format!("写入文件失败: {}", e))?;
-
-    Ok(())
+    db::with_conn(|conn| {
+        let tx = conn.transaction()?;
+        for template in templates {
+            upsert(&tx, template)?;
+        }
+        tx.commit()
+    })
 }
 
 /// 添加模板
 pub fn add_template(template: CommentTemplate) -> Result<(), String> {
-    let mut templates = load_templates();
-    templates.push(template);
-    save_templates(&templates)
+    db::with_conn(|conn| upsert(conn, &template))
 }
 
 /// 更新模板
 pub fn update_template(template: CommentTemplate) -> Result<(), String> {
-    let mut templates = load_templates();
+    let updated = db::with_conn(|conn| {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM templates WHERE id = ?1)",
+            [&template.id],
+            |row| row.get(0),
+        )?;
+        if exists {
+            upsert(conn, &template)?;
+        }
+        Ok(exists)
+    })?;
 
-    if let Some(pos) = templates.iter().position(|t| t.id == template.id) {
-        templates[pos] = template;
-        save_templates(&templates)
+    if updated {
+        Ok(())
     } else {
-        Err("模板不存在".to_string())
+        Err(i18n::t(Msg::TemplateNotFound).to_string())
     }
 }
 
 /// 删除模板
 pub fn delete_template(id: &str) -> Result<(), String> {
-    let mut templates = load_templates();
+    let deleted = db::with_conn(|conn| conn.execute("DELETE FROM templates WHERE id = ?1", [id]))?;
 
-    if let Some(pos) = templates.iter().position(|t| t.id == id) {
-        templates.remove(pos);
-        save_templates(&templates)
+    if deleted > 0 {
+        Ok(())
     } else {
-        Err("模板不存在".to_string())
+        Err(i18n::t(Msg::TemplateNotFound).to_string())
+    }
+}
+
+/// 记录模板被使用，返回模板当前的修订版本及其内容 (模板不存在时返回 None)
+pub fn mark_template_used(id: &str) -> Result<Option<(u32, String)>, String> {
+    db::with_conn(|conn| {
+        let updated = conn.execute(
+            "UPDATE templates SET last_used_at = ?1 WHERE id = ?2",
+            params![chrono::Utc::now().timestamp(), id],
+        )?;
+        if updated == 0 {
+            return Ok(None);
+        }
+        conn.query_row(
+            "SELECT revision, content FROM templates WHERE id = ?1",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map(Some)
+    })
+}
+
+/// 按条件筛选并排序模板
+pub fn filter_templates(
+    templates: Vec<CommentTemplate>,
+    filter: &TemplateFilter,
+) -> Vec<CommentTemplate> {
+    let terms: Vec<String> = filter
+        .query
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut result: Vec<CommentTemplate> = templates
+        .into_iter()
+        .filter(|t| {
+            filter
+                .category
+                .as_ref()
+                .map_or(true, |c| t.category.as_ref() == Some(c))
+        })
+        .filter(|t| filter.tags.iter().all(|tag| t.tags.contains(tag)))
+        .filter(|t| {
+            let haystack = format!("{}\n{}", t.name, t.content).to_lowercase();
+            terms.iter().all(|term| haystack.contains(term.as_str()))
+        })
+        .collect();
+
+    match filter.sort {
+        Some(TemplateSort::LastUsed) => {
+            result.sort_by_key(|t| std::cmp::Reverse(t.last_used_at.unwrap_or(i64::MIN)))
+        }
+        Some(TemplateSort::UpdatedAt) => result.sort_by_key(|t| std::cmp::Reverse(t.updated_at)),
+        Some(TemplateSort::CreatedAt) => result.sort_by_key(|t| std::cmp::Reverse(t.created_at)),
+        Some(TemplateSort::Name) => result.sort_by(|a, b| a.name.cmp(&b.name)),
+        None => {}
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, created_at: i64) -> CommentTemplate {
+        CommentTemplate {
+            id: id.to_string(),
+            name: id.to_string(),
+            content: "{title}".to_string(),
+            category: None,
+            tags: vec!["tag".to_string()],
+            created_at,
+            updated_at: created_at,
+            last_used_at: None,
+            revision: 1,
+        }
+    }
+
+    #[test]
+    fn skips_and_reports_bad_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(db::MIGRATIONS[0]).unwrap();
+        upsert(&conn, &template("good", 1)).unwrap();
+        upsert(&conn, &template("bad", 2)).unwrap();
+        conn.execute(
+            "UPDATE templates SET tags = 'not json' WHERE id = 'bad'",
+            [],
+        )
+        .unwrap();
+
+        let mut stmt = conn.prepare(SELECT_TEMPLATE).unwrap();
+        let rows = stmt.query_map([], from_row).unwrap();
+        let loaded = db::collect_rows("templates", rows);
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, "good");
+        assert!(crate::storage::storage_issues()
+            .iter()
+            .any(|issue| issue.file.ends_with("(templates)")));
     }
 }
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Msg};
+use crate::models::{CommentTemplate, TemplateExport, TemplateFormat};
+
+/// CSV 中多个标签之间的分隔符
+const CSV_TAG_SEPARATOR: char = ';';
+
+/// CSV 行 (标签合并为一列)
+#[derive(Serialize, Deserialize)]
+struct CsvRow {
+    #[serde(default)]
+    id: String,
+    name: String,
+    content: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    tags: String,
+}
+
+impl From<&CommentTemplate> for TemplateExport {
+    fn from(template: &CommentTemplate) -> Self {
+        Self {
+            id: Some(template.id.clone()),
+            name: template.name.clone(),
+            content: template.content.clone(),
+            category: template.category.clone(),
+            tags: template.tags.clone(),
+        }
+    }
+}
+
+impl TryFrom<TemplateExport> for CsvRow {
+    type Error = String;
+
+    /// 标签中包含分隔符时无法在导入时还原，拒绝导出
+    fn try_from(template: TemplateExport) -> Result<Self, String> {
+        if let Some(tag) = template.tags.iter().find(|t| t.contains(CSV_TAG_SEPARATOR)) {
+            return Err(i18n::tf(
+                Msg::TemplateCsvTagSeparator,
+                &[&template.name, tag],
+            ));
+        }
+
+        Ok(Self {
+            id: template.id.unwrap_or_default(),
+            name: template.name,
+            content: template.content,
+            category: template.category.unwrap_or_default(),
+            tags: template.tags.join(&CSV_TAG_SEPARATOR.to_string()),
+        })
+    }
+}
+
+impl From<CsvRow> for TemplateExport {
+    fn from(row: CsvRow) -> Self {
+        let non_empty = |s: String| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Self {
+            id: non_empty(row.id),
+            name: row.name,
+            content: row.content,
+            category: non_empty(row.category),
+            tags: row
+                .tags
+                .split(CSV_TAG_SEPARATOR)
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// 将模板序列化为指定格式
+pub fn export_templates(
+    templates: &[CommentTemplate],
+    format: TemplateFormat,
+) -> Result<String, String> {
+    let exports: Vec<TemplateExport> = templates.iter().map(TemplateExport::from).collect();
+
+    match format {
+        TemplateFormat::Json => {
+            serde_json::to_string_pretty(&exports).map_err(|e| format!("序列化失败: {}", e))
+        }
+        TemplateFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for template in exports {
+                writer
+                    .serialize(CsvRow::try_from(template)?)
+                    .map_err(|e| format!("序列化失败: {}", e))?;
+            }
+            let bytes = writer
+                .into_inner()
+                .map_err(|e| format!("序列化失败: {}", e))?;
+            String::from_utf8(bytes).map_err(|e| format!("序列化失败: {}", e))
+        }
+    }
+}
+
+/// 解析后的导入数据
+#[derive(Debug, Default)]
+pub struct ParsedTemplates {
+    pub templates: Vec<TemplateExport>,
+    /// 无法解析的行 (只有 CSV 会逐行报告，其余行照常导入)
+    pub errors: Vec<String>,
+}
+
+/// 解析导入的模板数据
+pub fn parse_templates(data: &str, format: TemplateFormat) -> Result<ParsedTemplates, String> {
+    match format {
+        TemplateFormat::Json => {
+            let templates = serde_json::from_str(data).map_err(|e| format!("解析失败: {}", e))?;
+            Ok(ParsedTemplates {
+                templates,
+                errors: Vec::new(),
+            })
+        }
+        TemplateFormat::Csv => {
+            // 兼容 Excel 导出的带 BOM 的 UTF-8 文件
+            let data = data.trim_start_matches('\u{feff}');
+            let mut parsed = ParsedTemplates::default();
+            let mut reader = csv::Reader::from_reader(data.as_bytes());
+            for (i, row) in reader.deserialize::<CsvRow>().enumerate() {
+                match row {
+                    Ok(row) => parsed.templates.push(TemplateExport::from(row)),
+                    // 行号从 1 开始，且第 1 行是表头
+                    Err(e) => parsed
+                        .errors
+                        .push(i18n::tf(Msg::TemplateCsvRowInvalid, &[&(i + 2), &e])),
+                }
+            }
+            Ok(parsed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, name: &str, content: &str) -> CommentTemplate {
+        CommentTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            content: content.to_string(),
+            category: None,
+            tags: Vec::new(),
+            created_at: 0,
+            updated_at: 0,
+            last_used_at: None,
+            revision: 1,
+        }
+    }
+
+    fn samples() -> Vec<CommentTemplate> {
+        let mut tagged = template("a", "问候, 通用", "你好，{title}！\n第二行 \"引号\", 逗号");
+        tagged.category = Some("日常".to_string());
+        tagged.tags = vec!["打招呼".to_string(), "tag, with comma".to_string()];
+        vec![tagged, template("b", "空分类", "只有内容")]
+    }
+
+    /// 比较导出前后的模板内容 (不含使用记录等本地信息)
+    fn assert_same(parsed: &[TemplateExport], templates: &[CommentTemplate]) {
+        assert_eq!(parsed.len(), templates.len());
+        for (p, t) in parsed.iter().zip(templates) {
+            assert_eq!(p.id.as_deref(), Some(t.id.as_str()));
+            assert_eq!(p.name, t.name);
+            assert_eq!(p.content, t.content);
+            assert_eq!(p.category, t.category);
+            assert_eq!(p.tags, t.tags);
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let templates = samples();
+        let data = export_templates(&templates, TemplateFormat::Json).unwrap();
+        let parsed = parse_templates(&data, TemplateFormat::Json).unwrap();
+        assert!(parsed.errors.is_empty());
+        assert_same(&parsed.templates, &templates);
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let templates = samples();
+        let data = export_templates(&templates, TemplateFormat::Csv).unwrap();
+        let parsed = parse_templates(&data, TemplateFormat::Csv).unwrap();
+        assert!(parsed.errors.is_empty());
+        assert_same(&parsed.templates, &templates);
+    }
+
+    #[test]
+    fn csv_accepts_bom_and_missing_optional_columns() {
+        let parsed =
+            parse_templates("\u{feff}name,content\n名称,内容\n", TemplateFormat::Csv).unwrap();
+        assert_eq!(parsed.templates.len(), 1);
+        assert_eq!(parsed.templates[0].id, None);
+        assert_eq!(parsed.templates[0].category, None);
+        assert!(parsed.templates[0].tags.is_empty());
+    }
+
+    #[test]
+    fn csv_rejects_tag_with_separator() {
+        let mut bad = template("a", "分号", "内容");
+        bad.tags = vec!["a;b".to_string()];
+        assert!(export_templates(&[bad.clone()], TemplateFormat::Csv).is_err());
+        // JSON 不受影响
+        assert!(export_templates(&[bad], TemplateFormat::Json).is_ok());
+    }
+
+    #[test]
+    fn csv_reports_bad_rows_and_keeps_others() {
+        let data = "id,name,content,category,tags\n\
+                    1,第一个,内容一,,\n\
+                    2,只有两列\n\
+                    3,第三个,内容三,分类,a;b\n";
+        let parsed = parse_templates(data, TemplateFormat::Csv).unwrap();
+        assert_eq!(parsed.errors.len(), 1);
+        let names: Vec<&str> = parsed.templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["第一个", "第三个"]);
+        assert_eq!(parsed.templates[1].tags, ["a", "b"]);
+    }
+}
@@ -0,0 +1,83 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::models::{CommentTemplate, TemplateRevision};
+
+use super::db;
+
+const SELECT_REVISION: &str =
+    "SELECT template_id, revision, name, content, created_at FROM template_revisions";
+
+/// 从查询结果行构造修订版本
+fn from_row(row: &Row) -> rusqlite::Result<TemplateRevision> {
+    Ok(TemplateRevision {
+        template_id: row.get(0)?,
+        revision: row.get(1)?,
+        name: row.get(2)?,
+        content: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+/// 写入修订版本 (同一版本已存在时忽略)
+pub(super) fn insert(conn: &Connection, revision: &TemplateRevision) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO template_revisions (template_id, revision, name, content, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            revision.template_id,
+            revision.revision,
+            revision.name,
+            revision.content,
+            revision.created_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 记录模板当前内容为一个修订版本 (同一版本已存在时忽略)
+pub fn record_revision(template: &CommentTemplate) -> Result<(), String> {
+    let revision = TemplateRevision {
+        template_id: template.id.clone(),
+        revision: template.revision,
+        name: template.name.clone(),
+        content: template.content.clone(),
+        created_at: template.updated_at,
+    };
+    db::with_conn(|conn| insert(conn, &revision))
+}
+
+/// 获取模板的所有修订版本 (最新的在前，无法读取的版本会被跳过并记录问题)
+pub fn get_revisions(template_id: &str) -> Result<Vec<TemplateRevision>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE template_id = ?1 ORDER BY revision DESC",
+            SELECT_REVISION
+        ))?;
+        let rows = stmt.query_map([template_id], from_row)?;
+        Ok(db::collect_rows("template_revisions", rows))
+    })
+}
+
+/// 获取模板的指定修订版本
+pub fn get_revision(template_id: &str, revision: u32) -> Result<Option<TemplateRevision>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE template_id = ?1 AND revision = ?2",
+            SELECT_REVISION
+        ))?;
+        let mut rows = stmt.query_map(params![template_id, revision], from_row)?;
+        rows.next().transpose()
+    })
+}
+
+/// 获取所有模板的修订版本
+pub fn load_all_revisions() -> Result<Vec<TemplateRevision>, String> {
+    db::with_conn(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{} ORDER BY template_id, revision",
+            SELECT_REVISION
+        ))?;
+        let rows = stmt.query_map([], from_row)?;
+        rows.collect()
+    })
+}
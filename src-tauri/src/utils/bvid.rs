@@ -0,0 +1,104 @@
+// BV 号与 AV 号互转 (离线计算，与 B站 当前的编码算法一致)
+
+/// BV 号编码字符表
+const ALPHABET: &[u8; 58] = b"FcwAPNKTMug3GV5Lj7EJnHpWsx4tb8haYeviqBz6rkCy12mUSDQX9RdoZf";
+
+const XOR_CODE: u64 = 23442827791579;
+const MASK_CODE: u64 = 2251799813685247;
+const MAX_AID: u64 = 1 << 51;
+const BASE: u64 = 58;
+
+/// BV 号固定长度 (含 "BV1" 前缀)
+const BVID_LEN: usize = 12;
+
+/// 编码时交换的字符位置
+const SWAPS: [(usize, usize); 2] = [(3, 9), (4, 7)];
+
+/// AV 号转 BV 号，超出范围的 AV 号返回 None
+pub fn av_to_bv(aid: u64) -> Option<String> {
+    if aid == 0 || aid >= MAX_AID {
+        return None;
+    }
+
+    let mut bytes = *b"BV1000000000";
+    let mut tmp = (MAX_AID | aid) ^ XOR_CODE;
+    let mut index = BVID_LEN - 1;
+    while tmp > 0 {
+        bytes[index] = ALPHABET[(tmp % BASE) as usize];
+        tmp /= BASE;
+        index -= 1;
+    }
+    for (a, b) in SWAPS {
+        bytes.swap(a, b);
+    }
+
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+/// BV 号转 AV 号，格式不正确时返回 None (前缀 "BV" 不区分大小写)
+pub fn bv_to_av(bvid: &str) -> Option<u64> {
+    let bytes = bvid.as_bytes();
+    if bytes.len() != BVID_LEN || !bytes[..2].eq_ignore_ascii_case(b"BV") || bytes[2] != b'1' {
+        return None;
+    }
+
+    let mut bytes = bytes.to_vec();
+    for (a, b) in SWAPS {
+        bytes.swap(a, b);
+    }
+
+    let mut tmp: u64 = 0;
+    for &c in &bytes[3..] {
+        let digit = ALPHABET.iter().position(|&x| x == c)? as u64;
+        tmp = tmp.checked_mul(BASE)?.checked_add(digit)?;
+    }
+
+    let aid = (tmp & MASK_CODE) ^ XOR_CODE;
+    (aid > 0).then_some(aid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_known_ids() {
+        assert_eq!(av_to_bv(2).as_deref(), Some("BV1xx411c7mD"));
+        assert_eq!(av_to_bv(170001).as_deref(), Some("BV17x411w7KC"));
+        assert_eq!(bv_to_av("BV1xx411c7mD"), Some(2));
+        assert_eq!(bv_to_av("BV17x411w7KC"), Some(170001));
+        // 前缀不区分大小写
+        assert_eq!(bv_to_av("bv1xx411c7mD"), Some(2));
+    }
+
+    #[test]
+    fn round_trips_whole_range() {
+        for aid in [1, 2, 170001, 1 << 32, MAX_AID - 1] {
+            let bvid = av_to_bv(aid).unwrap();
+            assert_eq!(bv_to_av(&bvid), Some(aid), "{}", bvid);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_aid() {
+        assert_eq!(av_to_bv(0), None);
+        assert_eq!(av_to_bv(MAX_AID), None);
+    }
+
+    #[test]
+    fn rejects_invalid_bvid() {
+        for bvid in [
+            "",
+            "BV1xx411c7m",
+            "BV1xx411c7mDD",
+            "AV1xx411c7mD",
+            "BV2xx411c7mD",
+            // 0 不在编码字符表中
+            "BV1xx411c7m0",
+            // 长度相同的多字节字符
+            "BV1视频视",
+        ] {
+            assert_eq!(bv_to_av(bvid), None, "{}", bvid);
+        }
+    }
+}
@@ -1 +1,4 @@
 // 工具函数模块
+
+pub mod bvid;
+pub mod video_url;
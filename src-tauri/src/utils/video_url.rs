@@ -0,0 +1,173 @@
+// 视频链接和编号解析
+
+use url::Url;
+
+use super::bvid::bv_to_av;
+
+/// 短链接域名 (需要请求后跟随跳转才能得到视频地址)
+const SHORT_LINK_HOSTS: &[&str] = &["b23.tv", "bili2233.cn"];
+
+/// 链接或编号的解析结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VideoLink {
+    /// 已确定的视频，`page` 为 `?p=` 指定的分 P
+    Video { aid: u64, page: Option<u32> },
+    /// 短链接，需要请求后根据跳转地址再次解析
+    ShortLink(String),
+}
+
+/// 解析视频编号: BV 号 (如 `BV1xx411c7mD`) 或 AV 号 (如 `av170001`)
+pub fn parse_video_id(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let prefix = text.get(..2)?;
+    if prefix.eq_ignore_ascii_case("bv") {
+        return bv_to_av(text);
+    }
+    if prefix.eq_ignore_ascii_case("av") {
+        return text[2..].parse().ok().filter(|&aid| aid > 0);
+    }
+    None
+}
+
+/// 分享文本中的第一个链接 (如 "【标题】 https://b23.tv/xxxx")
+fn find_url(text: &str) -> Option<&str> {
+    let start = text.find("http://").or_else(|| text.find("https://"))?;
+    let rest = &text[start..];
+    let end = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, '】' | '」' | '"' | '\''))
+        .unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// 视频页面链接中的分 P 参数
+fn page_param(url: &Url) -> Option<u32> {
+    url.query_pairs()
+        .find(|(key, _)| key == "p")
+        .and_then(|(_, value)| value.parse().ok())
+        .filter(|&p| p > 0)
+}
+
+/// 解析 B站 视频链接或编号
+///
+/// 支持 BV/AV 号、`www.` / `m.` 等 bilibili.com 视频页面 (含 `?p=` 分 P)、
+/// b23.tv 短链接，以及包含链接的分享文本
+pub fn parse_video_link(input: &str) -> Option<VideoLink> {
+    let input = input.trim();
+    if let Some(aid) = parse_video_id(input) {
+        return Some(VideoLink::Video { aid, page: None });
+    }
+
+    let raw = match find_url(input) {
+        Some(url) => url.to_string(),
+        // 省略了协议的链接 (如 "www.bilibili.com/video/BV...")
+        None if !input.contains(char::is_whitespace) => format!("https://{}", input),
+        None => return None,
+    };
+    let url = Url::parse(&raw).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+
+    if SHORT_LINK_HOSTS.contains(&host.as_str()) {
+        let first = segments.next()?;
+        return Some(match parse_video_id(first) {
+            Some(aid) => VideoLink::Video {
+                aid,
+                page: page_param(&url),
+            },
+            None => VideoLink::ShortLink(url.to_string()),
+        });
+    }
+
+    if host == "bilibili.com" || host.ends_with(".bilibili.com") {
+        let aid = segments
+            .skip_while(|s| *s != "video")
+            .nth(1)
+            .and_then(parse_video_id)?;
+        return Some(VideoLink::Video {
+            aid,
+            page: page_param(&url),
+        });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(aid: u64, page: Option<u32>) -> Option<VideoLink> {
+        Some(VideoLink::Video { aid, page })
+    }
+
+    #[test]
+    fn parses_video_ids() {
+        assert_eq!(parse_video_id(" BV1xx411c7mD "), Some(2));
+        assert_eq!(parse_video_id("av170001"), Some(170001));
+        assert_eq!(parse_video_id("AV170001"), Some(170001));
+        assert_eq!(parse_video_id("av0"), None);
+        assert_eq!(parse_video_id("av"), None);
+        assert_eq!(parse_video_id("视频"), None);
+        assert_eq!(parse_video_id("a视频"), None);
+    }
+
+    #[test]
+    fn parses_video_pages() {
+        assert_eq!(
+            parse_video_link("https://www.bilibili.com/video/BV1xx411c7mD?p=3"),
+            video(2, Some(3))
+        );
+        assert_eq!(
+            parse_video_link("https://m.bilibili.com/video/av170001"),
+            video(170001, None)
+        );
+        assert_eq!(
+            parse_video_link("https://www.bilibili.com/video/BV1xx411c7mD/?p=0"),
+            video(2, None)
+        );
+        // 省略协议
+        assert_eq!(
+            parse_video_link("www.bilibili.com/video/BV17x411w7KC/"),
+            video(170001, None)
+        );
+    }
+
+    #[test]
+    fn distinguishes_short_links() {
+        assert_eq!(
+            parse_video_link("https://b23.tv/abc123"),
+            Some(VideoLink::ShortLink("https://b23.tv/abc123".to_string()))
+        );
+        assert_eq!(
+            parse_video_link("https://b23.tv/BV1xx411c7mD?p=2"),
+            video(2, Some(2))
+        );
+    }
+
+    #[test]
+    fn extracts_link_from_share_text() {
+        assert_eq!(
+            parse_video_link("【视频标题】 https://b23.tv/abc123 点击观看"),
+            Some(VideoLink::ShortLink("https://b23.tv/abc123".to_string()))
+        );
+        assert_eq!(
+            parse_video_link("【视频标题】https://www.bilibili.com/video/BV1xx411c7mD】"),
+            video(2, None)
+        );
+    }
+
+    #[test]
+    fn rejects_other_input() {
+        assert_eq!(
+            parse_video_link("https://example.com/video/BV1xx411c7mD"),
+            None
+        );
+        assert_eq!(
+            parse_video_link("https://www.bilibili.com/read/cv123"),
+            None
+        );
+        assert_eq!(parse_video_link("不是 链接"), None);
+        assert_eq!(parse_video_link("视频"), None);
+        assert_eq!(parse_video_link(""), None);
+    }
+}
@@ -0,0 +1,345 @@
+// 候选视频筛选模块
+
+use regex::{Regex, RegexBuilder};
+
+use crate::i18n::{self, Msg};
+use crate::models::{FilterOutcome, RejectedVideo, VideoFilterRules, VideoItem};
+
+/// 解析视频时长字符串 (`"mm:ss"` 或 `"h:mm:ss"`) 为秒数，格式无效或数值溢出时返回 None
+pub fn parse_duration(duration: &str) -> Option<u64> {
+    let parts: Vec<&str> = duration.trim().split(':').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+
+    parts.iter().try_fold(0u64, |total, part| {
+        let value: u64 = part.trim().parse().ok()?;
+        total.checked_mul(60)?.checked_add(value)
+    })
+}
+
+/// 校验上下限
+fn check_bounds<T: PartialOrd>(name: &str, min: Option<T>, max: Option<T>) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(i18n::tf(Msg::InvalidFilterRange, &[&name])),
+        _ => Ok(()),
+    }
+}
+
+/// 规范化关键词列表: 去除首尾空白和空项，转为小写
+fn normalize_keywords(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .map(|w| w.trim().to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// 编译后的筛选规则
+pub struct VideoFilter {
+    rules: VideoFilterRules,
+    title_include: Vec<String>,
+    title_exclude: Vec<String>,
+    title_regex: Option<Regex>,
+    author_blocklist: Vec<String>,
+    author_allowlist: Vec<String>,
+}
+
+impl VideoFilter {
+    /// 校验并编译筛选规则，上下限颠倒或正则表达式无效时返回错误
+    pub fn compile(rules: &VideoFilterRules) -> Result<Self, String> {
+        check_bounds("play", rules.min_play, rules.max_play)?;
+        check_bounds("danmaku", rules.min_danmaku, rules.max_danmaku)?;
+        check_bounds("duration", rules.min_duration_secs, rules.max_duration_secs)?;
+
+        let title_regex = rules
+            .title_regex
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| i18n::tf(Msg::InvalidFilterRegex, &[&p, &e]))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            rules: rules.clone(),
+            title_include: normalize_keywords(&rules.title_include),
+            title_exclude: normalize_keywords(&rules.title_exclude),
+            title_regex,
+            author_blocklist: normalize_keywords(&rules.author_blocklist),
+            author_allowlist: normalize_keywords(&rules.author_allowlist),
+        })
+    }
+
+    /// UP主是否在列表中 (按名称或 mid 匹配)
+    fn author_in(list: &[String], video: &VideoItem) -> bool {
+        let name = video.author.trim().to_lowercase();
+        let mid = video.mid.to_string();
+        list.iter().any(|entry| *entry == name || *entry == mid)
+    }
+
+    /// 检查视频是否符合规则，不符合时返回原因
+    pub fn check(&self, video: &VideoItem, now: i64) -> Result<(), String> {
+        let rules = &self.rules;
+
+        if let Some(min) = rules.min_play.filter(|&min| video.play < min) {
+            return Err(i18n::tf(Msg::FilterPlayTooLow, &[&min]));
+        }
+        if let Some(max) = rules.max_play.filter(|&max| video.play > max) {
+            return Err(i18n::tf(Msg::FilterPlayTooHigh, &[&max]));
+        }
+        if let Some(min) = rules.min_danmaku.filter(|&min| video.danmaku < min) {
+            return Err(i18n::tf(Msg::FilterDanmakuTooLow, &[&min]));
+        }
+        if let Some(max) = rules.max_danmaku.filter(|&max| video.danmaku > max) {
+            return Err(i18n::tf(Msg::FilterDanmakuTooHigh, &[&max]));
+        }
+
+        if rules.min_duration_secs.is_some() || rules.max_duration_secs.is_some() {
+            let Some(duration) = parse_duration(&video.duration) else {
+                return Err(i18n::tf(Msg::FilterDurationUnknown, &[&video.duration]));
+            };
+            if let Some(min) = rules.min_duration_secs.filter(|&min| duration < min) {
+                return Err(i18n::tf(Msg::FilterDurationTooShort, &[&min]));
+            }
+            if let Some(max) = rules.max_duration_secs.filter(|&max| duration > max) {
+                return Err(i18n::tf(Msg::FilterDurationTooLong, &[&max]));
+            }
+        }
+
+        if let Some(days) = rules.max_age_days {
+            if now - video.pubdate > i64::from(days) * 24 * 3600 {
+                return Err(i18n::tf(Msg::FilterTooOld, &[&days]));
+            }
+        }
+
+        let title = video.title.to_lowercase();
+        if !self.title_include.is_empty() && !self.title_include.iter().any(|w| title.contains(w)) {
+            return Err(i18n::t(Msg::FilterTitleMissingKeyword).to_string());
+        }
+        if let Some(word) = self
+            .title_exclude
+            .iter()
+            .find(|w| title.contains(w.as_str()))
+        {
+            return Err(i18n::tf(Msg::FilterTitleExcluded, &[word]));
+        }
+        if let Some(re) = &self.title_regex {
+            if !re.is_match(&video.title) {
+                return Err(i18n::t(Msg::FilterTitleRegexMismatch).to_string());
+            }
+        }
+
+        if Self::author_in(&self.author_blocklist, video) {
+            return Err(i18n::tf(Msg::FilterAuthorBlocked, &[&video.author]));
+        }
+        if !self.author_allowlist.is_empty() && !Self::author_in(&self.author_allowlist, video) {
+            return Err(i18n::tf(Msg::FilterAuthorNotAllowed, &[&video.author]));
+        }
+
+        Ok(())
+    }
+
+    /// 按规则筛选视频，返回保留和排除 (含原因) 的视频
+    pub fn apply(&self, videos: Vec<VideoItem>) -> FilterOutcome {
+        let now = chrono::Utc::now().timestamp();
+        let mut outcome = FilterOutcome::default();
+
+        for video in videos {
+            match self.check(&video, now) {
+                Ok(()) => outcome.kept.push(video),
+                Err(reason) => outcome.rejected.push(RejectedVideo { video, reason }),
+            }
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("42"), Some(42));
+        assert_eq!(parse_duration("3:25"), Some(205));
+        assert_eq!(parse_duration("75:03"), Some(4503));
+        assert_eq!(parse_duration("1:02:03"), Some(3723));
+        assert_eq!(parse_duration(" 1 : 05 "), Some(65));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("1:2:3:4"), None);
+        assert_eq!(parse_duration("a:10"), None);
+        assert_eq!(parse_duration("-1:10"), None);
+        assert_eq!(parse_duration("1::10"), None);
+    }
+
+    #[test]
+    fn overflow_returns_none() {
+        assert_eq!(parse_duration("99999999999999999999:0"), None);
+        assert_eq!(parse_duration("999999999999999999:0:0"), None);
+        assert_eq!(parse_duration(&format!("{}:0", u64::MAX / 60 + 1)), None);
+    }
+
+    #[test]
+    fn rejects_reversed_bounds() {
+        let rules = VideoFilterRules {
+            min_play: Some(100),
+            max_play: Some(10),
+            ..VideoFilterRules::default()
+        };
+        assert!(VideoFilter::compile(&rules).is_err());
+    }
+
+    const NOW: i64 = 1_700_000_000;
+    const DAY: i64 = 24 * 3600;
+
+    fn video() -> VideoItem {
+        VideoItem {
+            aid: 1,
+            bvid: "BV1xx411c7mD".to_string(),
+            title: "Rust 入门教程 第一集".to_string(),
+            author: "Ferris".to_string(),
+            mid: 42,
+            pic: String::new(),
+            play: 1000,
+            danmaku: 50,
+            pubdate: NOW - 3 * DAY,
+            duration: "10:00".to_string(),
+            description: String::new(),
+            title_highlights: Vec::new(),
+        }
+    }
+
+    fn passes(rules: VideoFilterRules, video: &VideoItem) -> bool {
+        VideoFilter::compile(&rules)
+            .unwrap()
+            .check(video, NOW)
+            .is_ok()
+    }
+
+    #[test]
+    fn play_and_danmaku_bounds() {
+        let v = video();
+        let play = |min, max| VideoFilterRules {
+            min_play: min,
+            max_play: max,
+            ..VideoFilterRules::default()
+        };
+        assert!(passes(play(Some(1000), Some(1000)), &v));
+        assert!(!passes(play(Some(1001), None), &v));
+        assert!(!passes(play(None, Some(999)), &v));
+
+        let danmaku = |min, max| VideoFilterRules {
+            min_danmaku: min,
+            max_danmaku: max,
+            ..VideoFilterRules::default()
+        };
+        assert!(passes(danmaku(Some(50), Some(50)), &v));
+        assert!(!passes(danmaku(Some(51), None), &v));
+        assert!(!passes(danmaku(None, Some(49)), &v));
+    }
+
+    #[test]
+    fn duration_bounds() {
+        let rules = |min, max| VideoFilterRules {
+            min_duration_secs: min,
+            max_duration_secs: max,
+            ..VideoFilterRules::default()
+        };
+        let v = video();
+        assert!(passes(rules(Some(600), Some(600)), &v));
+        assert!(!passes(rules(Some(601), None), &v));
+        assert!(!passes(rules(None, Some(599)), &v));
+
+        // 时长无法解析时只在设置了时长规则时排除
+        let unknown = VideoItem {
+            duration: "未知".to_string(),
+            ..video()
+        };
+        assert!(!passes(rules(Some(1), None), &unknown));
+        assert!(passes(VideoFilterRules::default(), &unknown));
+    }
+
+    #[test]
+    fn max_age_days() {
+        let rules = |days| VideoFilterRules {
+            max_age_days: Some(days),
+            ..VideoFilterRules::default()
+        };
+        assert!(passes(rules(3), &video()));
+        assert!(!passes(rules(2), &video()));
+    }
+
+    #[test]
+    fn title_keywords_ignore_case() {
+        let rules = |include: &[&str], exclude: &[&str]| VideoFilterRules {
+            title_include: include.iter().map(|s| s.to_string()).collect(),
+            title_exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            ..VideoFilterRules::default()
+        };
+        let v = video();
+        assert!(passes(rules(&["RUST", "python"], &[]), &v));
+        assert!(!passes(rules(&["python"], &[]), &v));
+        assert!(!passes(rules(&[], &[" rust "]), &v));
+        // 空白关键词被忽略
+        assert!(passes(rules(&["  "], &[""]), &v));
+    }
+
+    #[test]
+    fn title_regex() {
+        let rules = |pattern: &str| VideoFilterRules {
+            title_regex: Some(pattern.to_string()),
+            ..VideoFilterRules::default()
+        };
+        let v = video();
+        assert!(passes(rules(r"^rust\s"), &v));
+        assert!(!passes(rules(r"第二集$"), &v));
+        assert!(VideoFilter::compile(&rules("(")).is_err());
+    }
+
+    #[test]
+    fn author_lists_match_name_or_mid() {
+        let rules = |block: &[&str], allow: &[&str]| VideoFilterRules {
+            author_blocklist: block.iter().map(|s| s.to_string()).collect(),
+            author_allowlist: allow.iter().map(|s| s.to_string()).collect(),
+            ..VideoFilterRules::default()
+        };
+        let v = video();
+        assert!(!passes(rules(&["ferris"], &[]), &v));
+        assert!(!passes(rules(&["42"], &[]), &v));
+        assert!(passes(rules(&["4"], &[]), &v));
+
+        assert!(passes(rules(&[], &["FERRIS"]), &v));
+        assert!(passes(rules(&[], &["42"]), &v));
+        assert!(!passes(rules(&[], &["someone else"]), &v));
+        // 屏蔽优先于允许
+        assert!(!passes(rules(&["42"], &["ferris"]), &v));
+    }
+
+    #[test]
+    fn apply_splits_kept_and_rejected() {
+        let filter = VideoFilter::compile(&VideoFilterRules {
+            min_play: Some(500),
+            ..VideoFilterRules::default()
+        })
+        .unwrap();
+        let low = VideoItem {
+            aid: 2,
+            play: 10,
+            ..video()
+        };
+        let outcome = filter.apply(vec![video(), low]);
+        assert_eq!(outcome.kept.len(), 1);
+        assert_eq!(outcome.rejected.len(), 1);
+        assert_eq!(outcome.rejected[0].video.aid, 2);
+    }
+}